@@ -0,0 +1,37 @@
+// Completion badges.
+//
+// When a plan completes, every member still in it who never defaulted or
+// missed a cycle gets a badge minted on the CW721 contract set in
+// `Config::badge_contract`. Its `on_time_bps` is the share of cycles the
+// member paid on time, counted in `ON_TIME_SHARES`. The mint message
+// mirrors cw721-base's `Mint` with `BadgeMetadata` as its extension. Badges
+// are meant to be soulbound, which this contract cannot enforce: the
+// configured contract must reject transfers.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// Reply id of badge mints; see `contract::reply`
+pub const BADGE_REPLY_ID: u64 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeExecuteMsg {
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: BadgeMetadata,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BadgeMetadata {
+    pub plan_id: u64,
+    pub cycles_completed: u64,
+    pub on_time_bps: u16, // share of those cycles paid on time
+}
+
+pub fn token_id(plan_id: u64, member: &str) -> String {
+    format!("ajo-{}-{}", plan_id, member)
+}
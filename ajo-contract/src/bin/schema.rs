@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use ajo_contract::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use ajo_contract::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
     }
 }
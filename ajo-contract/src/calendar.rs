@@ -0,0 +1,215 @@
+// Cycle boundaries for every `Frequency`.
+//
+// Fixed-length frequencies are plain second offsets from the plan start.
+// Month-based ones step through the UTC calendar: cycle `n` starts `n` months
+// after the anchor at the same time of day, on the same day of month, clamped
+// to the last day of shorter months (Jan 31 -> Feb 28 -> Mar 31). Every
+// boundary is computed from the anchor, so clamping never accumulates drift.
+
+use crate::state::Frequency;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+// Length of one cycle in seconds, or `None` for calendar-month frequencies
+pub fn fixed_period(frequency: &Frequency) -> Option<u64> {
+    match frequency {
+        Frequency::Daily => Some(SECONDS_PER_DAY),
+        Frequency::Weekly => Some(7 * SECONDS_PER_DAY),
+        Frequency::BiWeekly => Some(14 * SECONDS_PER_DAY),
+        Frequency::Custom { seconds } => Some(*seconds),
+        Frequency::Monthly | Frequency::Quarterly => None,
+    }
+}
+
+fn months_per_cycle(frequency: &Frequency) -> u64 {
+    match frequency {
+        Frequency::Quarterly => 3,
+        _ => 1,
+    }
+}
+
+// Timestamp at which `cycle` begins for a plan anchored at `anchor`
+pub fn cycle_start(frequency: &Frequency, anchor: u64, cycle: u64) -> u64 {
+    match fixed_period(frequency) {
+        Some(period) => anchor.saturating_add(cycle.saturating_mul(period)),
+        None => shift_months(anchor, (cycle * months_per_cycle(frequency)) as i64),
+    }
+}
+
+// Number of whole cycles that have elapsed between `anchor` and `now`
+pub fn cycles_between(frequency: &Frequency, anchor: u64, now: u64) -> u64 {
+    if now <= anchor {
+        return 0;
+    }
+    if let Some(period) = fixed_period(frequency) {
+        return (now - anchor) / period.max(1);
+    }
+
+    // Estimate from the calendar month difference, then correct for the day
+    // of month and time of day.
+    let (ay, am, _) = civil_from_days((anchor / SECONDS_PER_DAY) as i64);
+    let (ny, nm, _) = civil_from_days((now / SECONDS_PER_DAY) as i64);
+    let months = ((ny - ay) * 12 + (nm as i64 - am as i64)).max(0) as u64;
+    let mut cycle = months / months_per_cycle(frequency);
+    while cycle > 0 && cycle_start(frequency, anchor, cycle) > now {
+        cycle -= 1;
+    }
+    while cycle_start(frequency, anchor, cycle + 1) <= now {
+        cycle += 1;
+    }
+    cycle
+}
+
+// Anchor such that `cycle` begins exactly at `at`
+pub fn anchor_for(frequency: &Frequency, at: u64, cycle: u64) -> u64 {
+    match fixed_period(frequency) {
+        Some(period) => at.saturating_sub(cycle.saturating_mul(period)),
+        None => shift_months(at, -((cycle * months_per_cycle(frequency)) as i64)),
+    }
+}
+
+// Moves `timestamp` by `months` calendar months, keeping the time of day and
+// clamping the day to the length of the target month
+fn shift_months(timestamp: u64, months: i64) -> u64 {
+    let days = (timestamp / SECONDS_PER_DAY) as i64;
+    let time_of_day = timestamp % SECONDS_PER_DAY;
+    let (year, month, day) = civil_from_days(days);
+
+    let total = year * 12 + (month as i64 - 1) + months;
+    let year = total.div_euclid(12);
+    let month = (total.rem_euclid(12) + 1) as u32;
+    let day = day.min(days_in_month(year, month));
+
+    let days = days_from_civil(year, month, day).max(0) as u64;
+    days * SECONDS_PER_DAY + time_of_day
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ if is_leap_year(year) => 29,
+        _ => 28,
+    }
+}
+
+// Days since 1970-01-01 for a proleptic Gregorian date
+// (http://howardhinnant.github.io/date_algorithms.html#days_from_civil)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Timestamp of `year-month-day hour:00` UTC
+    fn at(year: i64, month: u32, day: u32, hour: u64) -> u64 {
+        days_from_civil(year, month, day) as u64 * SECONDS_PER_DAY + hour * 3_600
+    }
+
+    #[test]
+    fn civil_dates_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        assert_eq!(days_from_civil(2100, 3, 1), 47_541);
+
+        for days in -800..80_000 {
+            let (year, month, day) = civil_from_days(days);
+            assert!(day >= 1 && day <= days_in_month(year, month));
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn leap_years_follow_the_gregorian_rules() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(2100));
+        assert!(!is_leap_year(2023));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 28) + 1), (2024, 2, 29));
+        assert_eq!(civil_from_days(days_from_civil(2100, 2, 28) + 1), (2100, 3, 1));
+    }
+
+    #[test]
+    fn month_shifts_clamp_to_shorter_months() {
+        assert_eq!(shift_months(at(2023, 1, 31, 9), 1), at(2023, 2, 28, 9));
+        assert_eq!(shift_months(at(2024, 1, 31, 9), 1), at(2024, 2, 29, 9));
+        assert_eq!(shift_months(at(2024, 1, 31, 9), 2), at(2024, 3, 31, 9));
+        assert_eq!(shift_months(at(2024, 3, 31, 9), -1), at(2024, 2, 29, 9));
+        assert_eq!(shift_months(at(2024, 11, 30, 0), 3), at(2025, 2, 28, 0));
+        assert_eq!(shift_months(at(2024, 2, 29, 0), 12), at(2025, 2, 28, 0));
+        assert_eq!(shift_months(at(2024, 2, 29, 0), 48), at(2028, 2, 29, 0));
+    }
+
+    #[test]
+    fn cycles_start_from_the_anchor_without_drift() {
+        let anchor = at(2024, 1, 31, 12);
+        let monthly: Vec<u64> = (0..4).map(|cycle| cycle_start(&Frequency::Monthly, anchor, cycle)).collect();
+        assert_eq!(monthly, vec![anchor, at(2024, 2, 29, 12), at(2024, 3, 31, 12), at(2024, 4, 30, 12)]);
+
+        let quarterly: Vec<u64> = (0..4).map(|cycle| cycle_start(&Frequency::Quarterly, anchor, cycle)).collect();
+        assert_eq!(quarterly, vec![anchor, at(2024, 4, 30, 12), at(2024, 7, 31, 12), at(2024, 10, 31, 12)]);
+
+        let custom = Frequency::Custom { seconds: 3_600 };
+        assert_eq!(cycle_start(&custom, anchor, 5), anchor + 5 * 3_600);
+        assert_eq!(cycle_start(&Frequency::BiWeekly, anchor, 2), at(2024, 2, 28, 12));
+    }
+
+    #[test]
+    fn cycles_between_counts_whole_cycles_at_exact_boundaries() {
+        let anchor = at(2024, 1, 31, 12);
+        for frequency in [Frequency::Monthly, Frequency::Quarterly, Frequency::Weekly] {
+            assert_eq!(cycles_between(&frequency, anchor, anchor - 1), 0);
+            assert_eq!(cycles_between(&frequency, anchor, anchor), 0);
+            for cycle in 1..30 {
+                let boundary = cycle_start(&frequency, anchor, cycle);
+                assert_eq!(cycles_between(&frequency, anchor, boundary - 1), cycle - 1);
+                assert_eq!(cycles_between(&frequency, anchor, boundary), cycle);
+            }
+        }
+        // Feb 29 12:00 opens cycle 1, not a second earlier
+        assert_eq!(cycles_between(&Frequency::Monthly, anchor, at(2024, 2, 29, 11)), 0);
+        assert_eq!(cycles_between(&Frequency::Monthly, anchor, at(2024, 2, 29, 12)), 1);
+    }
+
+    #[test]
+    fn anchor_for_inverts_cycle_start() {
+        let at_cycle = at(2024, 5, 31, 8);
+        for frequency in [Frequency::Daily, Frequency::Weekly, Frequency::Custom { seconds: 90 }] {
+            let anchor = anchor_for(&frequency, at_cycle, 7);
+            assert_eq!(cycle_start(&frequency, anchor, 7), at_cycle);
+        }
+        // Month-based anchors step back by calendar months
+        assert_eq!(anchor_for(&Frequency::Monthly, at_cycle, 3), at(2024, 2, 29, 8));
+        assert_eq!(anchor_for(&Frequency::Quarterly, at(2024, 8, 15, 8), 2), at(2024, 2, 15, 8));
+        let anchor = anchor_for(&Frequency::Quarterly, at(2024, 8, 15, 8), 2);
+        assert_eq!(cycles_between(&Frequency::Quarterly, anchor, at(2024, 8, 15, 8)), 2);
+    }
+}
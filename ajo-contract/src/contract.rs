@@ -1,37 +1,202 @@
 // src/contract.rs
 
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, Binary, Deps, 
-	DepsMut, Env, MessageInfo, Response, StdError, 
-	StdResult, Uint128, BankMsg, Coin
+    entry_point, from_json, to_json_binary, wasm_execute, Addr, Api, Binary, Deps, 
+	DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, 
+	StdResult, Storage, SubMsg, SubMsgResult, Uint128, BankMsg, Coin, CosmosMsg, WasmMsg
 };
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom, UncheckedDenom};
 
+use crate::badge::{self, BadgeExecuteMsg, BadgeMetadata};
+use crate::calendar;
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, 
+use crate::schedule;
+use crate::trust;
+use crate::msg::{ContributeHookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
 	PlanResponse, QueryMsg, JoinRequestsResponse, 
-	ParticipantCycleStatusResponse};
-use crate::state::{Config, Frequency, JoinRequest, Plan, CONFIG, CONTRIBUTIONS, JOIN_REQUESTS, PARTICIPANT_START, PLANS, PLANS_BY_CREATOR, PLAN_COUNT, TRUST_SCORE, USER_DEBT};
+	AssetHoldings, AuctionResponse, Bid, InsurancePoolResponse, JoinEligibilityResponse, AttesterResponse, PlanTrustBreakdown, TrustBreakdownResponse, TrustHistoryEntry, TrustHistoryResponse, PoolAsset, SolvencyResponse, LotteryDrawResponse, ParticipantCycleStatusResponse, PayoutScheduleResponse, RefundResponse};
+use crate::reputation::{self, ReputationResponse};
+use crate::state::{Attestation, Badge, BadgeStatus, Config, FeeDestination, Frequency, Guarantee, InsuranceClaim, InsurancePool, JoinPolicy, JoinRequest, LateFee, LegacyConfig, LegacyPlan, LotteryDraw, MemberDefaulted, Payout, PayoutMode, PayoutOrder, Plan, PlanStatus, PoolReserve, TrustReason, ATTESTATIONS, ATTESTERS, BADGES, BIDS, CANCEL_VOTES, COLLATERAL, CONFIG, CONTRIBUTIONS, CREDITS, CYCLE_POT, DEBT_OWED, DEFAULTS, DIVIDENDS, DRAW_COMMITS, DRAW_REVEALS, GUARANTEES, INSURANCE_CLAIMS, INSURANCE_POOL, JOIN_REQUESTS, LATE_FEES, LOTTERY_DRAWS, MISSED_CYCLES, ON_TIME_SHARES, PARTICIPANT_START, PAYOUTS, PENDING_PAYOUTS, PLANS, PLEDGED_TRUST, PLANS_BY_CREATOR, PLAN_COUNT, REFUNDS, TRUST_EVENTS, TRUST_SCORE, TRUST_WAIVERS, USER_DEBT};
 use cw2::set_contract_version;
+use cw_storage_plus::{Bound, Map};
 
 const CONTRACT_NAME: &str = "crates.io:ajo-contract";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const MAX_TOTAL_CYCLES: u32 = 1_200;
+const MIN_CUSTOM_PERIOD: u64 = 3_600;
+const DRAW_COMMIT_WINDOW: u64 = 86_400;
+const DRAW_REVEAL_WINDOW: u64 = 86_400;
+// Denom of every plan created before plans chose their own
+const DEFAULT_DENOM: &str = "uxion";
+const DEFAULT_PAGE_LIMIT: u32 = 30;
+const MAX_PAGE_LIMIT: u32 = 100;
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    let allowed_denoms = match msg.allowed_denoms {
+        Some(denoms) => denoms
+            .into_iter()
+            .map(|denom| check_denom(deps.as_ref(), denom))
+            .collect::<Result<_, _>>()?,
+        None => vec![Denom::Native(DEFAULT_DENOM.to_string())],
+    };
     let config = Config {
         admin: info.sender.clone(),
+        allowed_denoms,
+        badge_contract: None,
     };
     CONFIG.save(deps.storage, &config)?;
+    INSURANCE_POOL.save(deps.storage, &InsurancePool::default())?;
     PLAN_COUNT.save(deps.storage, &0)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::new().add_attribute("method", "instantiate"))
 }
 
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let now = env.block.time.seconds();
+    let plan_count = PLAN_COUNT.may_load(deps.storage)?.unwrap_or_default();
+
+    if let Some(raw) = deps.storage.get(CONFIG.as_slice()) {
+        if from_json::<Config>(&raw).is_err() {
+            let legacy: LegacyConfig = from_json(&raw)?;
+            CONFIG.save(
+                deps.storage,
+                &Config {
+                    admin: legacy.admin,
+                    allowed_denoms: vec![Denom::Native(DEFAULT_DENOM.to_string())],
+                    badge_contract: None,
+                },
+            )?;
+        }
+    }
+
+    if INSURANCE_POOL.may_load(deps.storage)?.is_none() {
+        INSURANCE_POOL.save(deps.storage, &InsurancePool::default())?;
+    }
+
+    let mut migrated = 0u64;
+    for plan_id in 1..=plan_count {
+        let Some(raw) = deps.storage.get(&PLANS.key(plan_id)) else {
+            continue;
+        };
+        if from_json::<Plan>(&raw).is_ok() {
+            continue;
+        }
+        let legacy: LegacyPlan = from_json(&raw)?;
+        let plan = migrate_legacy_plan(deps.storage, now, legacy)?;
+        PLANS.save(deps.storage, plan_id, &plan)?;
+        migrated += 1;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("migrated_plans", migrated.to_string()))
+}
+
+// Legacy plans ran one clock per member (PARTICIPANT_START) and paid out from
+// `balance` alone. The shared clock is anchored so that cycle `current_cycle`
+// (the next payout) opens now; each member's open personal bucket and the
+// unpaid balance are carried onto that cycle.
+fn migrate_legacy_plan(
+    storage: &mut dyn Storage,
+    now: u64,
+    legacy: LegacyPlan,
+) -> Result<Plan, ContractError> {
+    let status = if !legacy.is_active {
+        PlanStatus::Completed
+    } else if legacy.participants.len() < 2 {
+        PlanStatus::Recruiting
+    } else {
+        PlanStatus::Active
+    };
+    let cycle = legacy.current_cycle as u64;
+    // Legacy clocks used fixed 30-day months and 4-week/30-day durations
+    let (period, cycles_per_month) = match legacy.frequency {
+        Frequency::Daily => (86_400, 30),
+        Frequency::Weekly => (7 * 86_400, 4),
+        _ => (30 * 86_400, 1),
+    };
+
+    for member in &legacy.participants {
+        let addr = Addr::unchecked(member);
+        let personal_start = PARTICIPANT_START.may_load(storage, (legacy.id, addr.clone()))?;
+        PARTICIPANT_START.remove(storage, (legacy.id, addr.clone()));
+
+        let Some(personal_start) = personal_start else {
+            continue;
+        };
+        let personal_cycle = now.saturating_sub(personal_start) / period;
+        if status != PlanStatus::Active || personal_cycle == cycle {
+            continue;
+        }
+        let open = CONTRIBUTIONS
+            .may_load(storage, (legacy.id, addr.clone(), personal_cycle))?
+            .unwrap_or_default();
+        if open.is_zero() {
+            continue;
+        }
+        CONTRIBUTIONS.remove(storage, (legacy.id, addr.clone(), personal_cycle));
+        CONTRIBUTIONS.update::<_, StdError>(storage, (legacy.id, addr, cycle), |existing| {
+            Ok(existing.unwrap_or_default() + open)
+        })?;
+    }
+
+    // Legacy payouts walked `participants` from `payout_index`; rotate so the
+    // next recipient stays the same under `payout_schedule[cycle % len]`
+    let n = legacy.participants.len().max(1);
+    let offset = (legacy.payout_index as usize + n - (cycle as usize % n)) % n;
+    let payout_schedule = (0..legacy.participants.len())
+        .map(|i| legacy.participants[(offset + i) % n].clone())
+        .collect();
+
+    let start_time = if status == PlanStatus::Active {
+        CYCLE_POT.save(storage, (legacy.id, cycle), &legacy.balance)?;
+        Some(calendar::anchor_for(&legacy.frequency, now, cycle))
+    } else {
+        None
+    };
+
+    Ok(Plan {
+        id: legacy.id,
+        name: legacy.name,
+        description: legacy.description,
+        total_participants: legacy.total_participants,
+        contribution_amount: legacy.contribution_amount,
+        denom: Denom::Native(DEFAULT_DENOM.to_string()),
+        frequency: legacy.frequency,
+        total_cycles: legacy.duration_months * cycles_per_month,
+        trust_score_required: legacy.trust_score_required.into(),
+        allow_partial: legacy.allow_partial,
+        participants: legacy.participants,
+        current_cycle: legacy.current_cycle,
+        status,
+        start_time,
+        payout_order: PayoutOrder::JoinOrder,
+        payout_mode: PayoutMode::RoundRobin,
+        auto_push: true, // legacy plans always pushed payouts
+        keeper_tip: Uint128::zero(),
+        keeper_reserve: Uint128::zero(),
+        grace_period_seconds: None,
+        late_fee: None,
+        late_fee_destination: FeeDestination::Pot,
+        collateral_amount: Uint128::zero(),
+        max_missed_cycles: None,
+        insured: false,
+        join_policy: JoinPolicy::Open,
+        payout_schedule,
+        balance: legacy.balance,
+        created_by: legacy.created_by,
+    })
+}
+
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
@@ -45,25 +210,59 @@ pub fn execute(
             description,
             total_participants,
             contribution_amount,
+            denom,
             frequency,
-            duration_months,
+            total_cycles,
             trust_score_required,
             allow_partial,
+            payout_order,
+            payout_mode,
+            auto_push,
+            keeper_tip,
+            grace_period_seconds,
+            late_fee,
+            late_fee_destination,
+            collateral_amount,
+            max_missed_cycles,
+            insured,
+            join_policy,
         } => execute_create_plan(
             deps,
-			env,
 			info,
             name,
             description,
             total_participants,
             contribution_amount,
+            denom,
             frequency,
-            duration_months,
+            total_cycles,
             trust_score_required,
             allow_partial,
+            payout_order,
+            payout_mode,
+            auto_push,
+            keeper_tip,
+            grace_period_seconds,
+            late_fee,
+            late_fee_destination,
+            collateral_amount,
+            max_missed_cycles,
+            insured,
+            join_policy,
         ),
-        ExecuteMsg::JoinPlan { plan_id } => execute_join_plan(deps, info, plan_id),
-		ExecuteMsg::RequestToJoinPlan { plan_id } => request_to_join_plan(deps, info, plan_id),
+        ExecuteMsg::JoinPlan { plan_id } => execute_join_plan(deps, env, info, plan_id),
+		ExecuteMsg::RequestToJoinPlan { plan_id } => execute_request_to_join_plan(deps, env, info, plan_id),
+		ExecuteMsg::DepositCollateral { plan_id } => execute_deposit_collateral(deps, info, plan_id),
+		ExecuteMsg::WithdrawCollateral { plan_id } => execute_withdraw_collateral(deps, info, plan_id),
+		ExecuteMsg::WaiveTrustRequirement { plan_id, member } => {
+			execute_waive_trust_requirement(deps, info, plan_id, member)
+		}
+		ExecuteMsg::Guarantee { plan_id, member, trust_pledge } => {
+			execute_guarantee(deps, env, info, plan_id, member, trust_pledge)
+		}
+		ExecuteMsg::WithdrawGuarantee { plan_id, member } => {
+			execute_withdraw_guarantee(deps, info, plan_id, member)
+		}
         ExecuteMsg::ApproveJoinRequest { plan_id, requester } => {
             approve_join_request(deps, env, info, plan_id, requester)
         },
@@ -73,38 +272,133 @@ pub fn execute(
 		ExecuteMsg::Contribute { plan_id, amount } => {
             execute_contribute(deps, env, info, plan_id, amount)
         }
+		ExecuteMsg::ContributeAhead { plan_id, cycles } => {
+			execute_contribute_ahead(deps, env, info, plan_id, cycles)
+		}
+		ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
+		ExecuteMsg::StartPlan { plan_id, start_at } => {
+			execute_start_plan(deps, env, info, plan_id, start_at)
+		}
+		ExecuteMsg::CancelPlan { plan_id } => execute_cancel_plan(deps, info, plan_id),
+		ExecuteMsg::CommitSecret { plan_id, commitment } => {
+			execute_commit_secret(deps, env, info, plan_id, commitment)
+		}
+		ExecuteMsg::RevealSecret { plan_id, secret } => {
+			execute_reveal_secret(deps, env, info, plan_id, secret)
+		}
+		ExecuteMsg::FinalizeDraw { plan_id } => execute_finalize_draw(deps, env, plan_id),
+		ExecuteMsg::PlaceBid { plan_id, discount } => {
+			execute_place_bid(deps, env, info, plan_id, discount)
+		}
+		ExecuteMsg::CloseAuction { plan_id } => execute_close_auction(deps, env, plan_id),
+		ExecuteMsg::SetPayoutOrder { plan_id, order } => {
+			execute_set_payout_order(deps, info, plan_id, order)
+		}
+		ExecuteMsg::ClaimRefund { plan_id } => execute_claim_refund(deps, info, plan_id),
+		ExecuteMsg::ClaimPayout { plan_id } => execute_claim_payout(deps, info, plan_id),
+		ExecuteMsg::AdvanceCycle { plan_id } => execute_advance_cycle(deps, env, info, plan_id),
+		ExecuteMsg::UpdateAllowedDenoms { add, remove } => {
+			execute_update_allowed_denoms(deps, info, add, remove)
+		}
+		ExecuteMsg::FundKeeperReserve { plan_id } => execute_fund_keeper_reserve(deps, info, plan_id),
+		ExecuteMsg::SetAutoPush { plan_id, enabled } => {
+			execute_set_auto_push(deps, info, plan_id, enabled)
+		}
+		ExecuteMsg::UpdateInsurancePool { premium_bps, max_cover_per_cycle, max_cover_per_plan } => {
+			execute_update_insurance_pool(deps, info, premium_bps, max_cover_per_cycle, max_cover_per_plan)
+		}
+		ExecuteMsg::SetBadgeContract { contract } => execute_set_badge_contract(deps, info, contract),
+		ExecuteMsg::SetAttester { attester, weight_bps } => execute_set_attester(deps, info, attester, weight_bps),
+		ExecuteMsg::SubmitAttestation { subject, delta, evidence_hash } => {
+			execute_submit_attestation(deps, env, info, subject, delta, evidence_hash)
+		}
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_create_plan(
     deps: DepsMut,
-	env: Env,
 	info: MessageInfo,
     name: String,
     description: String,
     total_participants: u32,
     contribution_amount: Uint128,
-    frequency: String,
-    duration_months: u32,
-    trust_score_required: u32,
+    denom: Option<UncheckedDenom>,
+    frequency: Frequency,
+    total_cycles: u32,
+    trust_score_required: u64,
     allow_partial: bool,
+    payout_order: Option<PayoutOrder>,
+    payout_mode: Option<PayoutMode>,
+    auto_push: Option<bool>,
+    keeper_tip: Option<Uint128>,
+    grace_period_seconds: Option<u64>,
+    late_fee: Option<LateFee>,
+    late_fee_destination: Option<FeeDestination>,
+    collateral_amount: Option<Uint128>,
+    max_missed_cycles: Option<u32>,
+    insured: Option<bool>,
+    join_policy: Option<JoinPolicy>,
 ) -> Result<Response, ContractError> {
     if !(2..=100).contains(&total_participants)
         || !(Uint128::from(10u128)..=Uint128::from(100000u128)).contains(&contribution_amount)
-        || !(1..=36).contains(&duration_months)
+        || !(1..=MAX_TOTAL_CYCLES).contains(&total_cycles)
         || !(3..=50).contains(&name.len())
         || !(10..=500).contains(&description.len())
     {
         return Err(ContractError::InvalidInput("Invalid input parameters".to_string()));
     }
 
-    let frequency = match frequency.as_str() {
-        "Daily" => Frequency::Daily,
-        "Weekly" => Frequency::Weekly,
-        "Monthly" => Frequency::Monthly,
-        _ => return Err(ContractError::InvalidInput("Invalid frequency".to_string())),
+    if let Frequency::Custom { seconds } = frequency {
+        if seconds < MIN_CUSTOM_PERIOD {
+            return Err(ContractError::InvalidInput("Invalid frequency".to_string()));
+        }
+    }
+
+    // The grace period must end inside the cycle (28 days for month-based ones)
+    if let Some(grace) = grace_period_seconds {
+        let shortest = calendar::fixed_period(&frequency).unwrap_or(28 * 86_400);
+        if grace >= shortest {
+            return Err(ContractError::InvalidInput("Grace period must be shorter than a cycle".to_string()));
+        }
+    }
+    match late_fee {
+        Some(LateFee::Flat { amount }) if amount.is_zero() => {
+            return Err(ContractError::InvalidInput("Invalid late fee".to_string()));
+        }
+        Some(LateFee::Bps { bps }) if bps == 0 || bps > 10_000 => {
+            return Err(ContractError::InvalidInput("Invalid late fee".to_string()));
+        }
+        _ => {}
+    }
+    if max_missed_cycles == Some(0) {
+        return Err(ContractError::InvalidInput("Missed-cycle limit must be at least one".to_string()));
+    }
+    match join_policy {
+        Some(JoinPolicy::Quorum { percent }) if percent == 0 || percent > 100 => {
+            return Err(ContractError::InvalidInput("Invalid join quorum".to_string()));
+        }
+        Some(JoinPolicy::Threshold { approvals }) if approvals == 0 || approvals >= total_participants => {
+            return Err(ContractError::InvalidInput("Invalid join threshold".to_string()));
+        }
+        _ => {}
+    }
+    let late_fee_destination = match late_fee_destination.unwrap_or(FeeDestination::Pot) {
+        FeeDestination::Treasury { address } => FeeDestination::Treasury {
+            address: deps.api.addr_validate(address.as_str())?,
+        },
+        FeeDestination::Recipient if payout_mode == Some(PayoutMode::Auction) => {
+            return Err(ContractError::InvalidInput("Auction plans have no next recipient for late fees".to_string()));
+        }
+        destination => destination,
     };
 
+    let denom = denom.unwrap_or_else(|| UncheckedDenom::Native(DEFAULT_DENOM.to_string()));
+    let denom = check_denom(deps.as_ref(), denom)?;
+    if !CONFIG.load(deps.storage)?.allowed_denoms.contains(&denom) {
+        return Err(ContractError::DenomNotAllowed { denom: denom_label(&denom) });
+    }
+
     let plan_id = PLAN_COUNT.load(deps.storage)? + 1;
     let participants = vec![info.sender.to_string()];
 
@@ -114,14 +408,28 @@ fn execute_create_plan(
         description,
         total_participants,
         contribution_amount,
+        denom,
         frequency,
-        duration_months,
+        total_cycles,
         trust_score_required,
         allow_partial,
         participants,
         current_cycle: 0,
-        is_active: true,
-        payout_index: 0,
+        status: PlanStatus::Recruiting,
+        start_time: None,
+        payout_order: payout_order.unwrap_or(PayoutOrder::JoinOrder),
+        payout_mode: payout_mode.unwrap_or(PayoutMode::RoundRobin),
+        auto_push: auto_push.unwrap_or(false),
+        keeper_tip: keeper_tip.unwrap_or_default(),
+        keeper_reserve: Uint128::zero(),
+        grace_period_seconds,
+        late_fee,
+        late_fee_destination,
+        collateral_amount: collateral_amount.unwrap_or_default(),
+        max_missed_cycles,
+        insured: insured.unwrap_or(false),
+        join_policy: join_policy.unwrap_or(JoinPolicy::Open),
+        payout_schedule: vec![],
         balance: Uint128::zero(),
 		created_by: info.sender.clone(),
     };
@@ -135,258 +443,2018 @@ fn execute_create_plan(
 		.unwrap_or_default();
 	ids.push(plan_id);
 	PLANS_BY_CREATOR.save(deps.storage, &info.sender, &ids)?;
-	let now = env.block.time.seconds();
-	PARTICIPANT_START.save(deps.storage, (plan_id, info.sender.clone()), &now)?;
 
     Ok(Response::new()
         .add_attribute("method", "create_plan")
-        .add_attribute("plan_id", plan_id.to_string()))
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("status", plan.status.to_string()))
 }
 
-fn execute_join_plan(deps: DepsMut, info: MessageInfo, plan_id: u64) -> Result<Response, ContractError> {
-    let mut plan = PLANS.load(deps.storage, plan_id)?;
-    let sender = info.sender.to_string();
+fn execute_join_plan(deps: DepsMut, env: Env, info: MessageInfo, plan_id: u64) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let deposit = funds_in(&info, &plan.denom)?;
+    join_plan(deps, env, info.sender, plan, deposit)
+}
 
-    if plan.is_active {
-        return Err(ContractError::PlanActive {});
-    }
+fn join_plan(
+    deps: DepsMut,
+    env: Env,
+    member: Addr,
+    mut plan: Plan,
+    deposit: Uint128,
+) -> Result<Response, ContractError> {
+    let plan_id = plan.id;
+    let sender = member.to_string();
+
+    ensure_recruiting(&plan)?;
     if plan.participants.len() as u32 >= plan.total_participants {
         return Err(ContractError::PlanFull {});
     }
     if plan.participants.contains(&sender) {
         return Err(ContractError::AlreadyParticipant {});
     }
-
-    let trust_score = 50; // TODO: integrate with real system
-    if trust_score < plan.trust_score_required {
-        return Err(ContractError::InsufficientTrustScore {});
+    if plan.join_policy != JoinPolicy::Open {
+        return Err(ContractError::Unauthorized("Plan requires approval to join; use RequestToJoinPlan".to_string()));
     }
 
+    ensure_trusted(deps.storage, &plan, &member, env.block.time.seconds())?;
+
+    let excess = escrow_collateral(deps.storage, &mut plan, &member, deposit)?;
     plan.participants.push(sender.clone());
     if plan.participants.len() as u32 == plan.total_participants {
-        plan.is_active = true;
+        // Quorum reached: everyone's cycle 0 starts now
+        start_plan(deps.storage, &env, &mut plan, env.block.time.seconds())?;
     }
 
     PLANS.save(deps.storage, plan_id, &plan)?;
-    Ok(Response::new()
+    let mut resp = Response::new();
+    if !excess.is_zero() {
+        resp = resp.add_message(send_funds(&plan, &member, excess)?);
+    }
+    Ok(resp
         .add_attribute("method", "join_plan")
         .add_attribute("plan_id", plan_id.to_string())
-        .add_attribute("participant", sender))
+        .add_attribute("participant", sender)
+        .add_attribute("status", plan.status.to_string()))
 }
 
-fn execute_contribute(
+// Tops `member`'s collateral up to `plan.collateral_amount` from `deposit`
+// and returns whatever was not needed
+fn escrow_collateral(
+    storage: &mut dyn Storage,
+    plan: &mut Plan,
+    member: &Addr,
+    deposit: Uint128,
+) -> Result<Uint128, ContractError> {
+    let held = COLLATERAL.may_load(storage, (plan.id, member.clone()))?.unwrap_or_default();
+    let needed = plan.collateral_amount.saturating_sub(held);
+    if deposit < needed {
+        return Err(ContractError::CollateralMissing { member: member.to_string() });
+    }
+    if !needed.is_zero() {
+        COLLATERAL.save(storage, (plan.id, member.clone()), &(held + needed))?;
+        plan.balance += needed;
+    }
+    Ok(deposit - needed)
+}
+
+fn execute_deposit_collateral(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
     plan_id: u64,
-    amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let mut plan = PLANS.load(deps.storage, plan_id)?;
-	let sender = info.sender.clone();
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let deposit = funds_in(&info, &plan.denom)?;
+    deposit_collateral(deps, info.sender, plan, deposit)
+}
 
-    if !plan.is_active {
-        return Err(ContractError::PlanNotActive {});
+fn deposit_collateral(
+    deps: DepsMut,
+    member: Addr,
+    mut plan: Plan,
+    deposit: Uint128,
+) -> Result<Response, ContractError> {
+    if plan.status.is_finished() {
+        return Err(ContractError::PlanFinished { status: plan.status.to_string() });
     }
-    if !plan.participants.contains(&sender.to_string()) {
+    if !plan.participants.contains(&member.to_string()) {
         return Err(ContractError::NotParticipant {});
     }
+    let held = COLLATERAL.may_load(deps.storage, (plan.id, member.clone()))?.unwrap_or_default();
+    if held >= plan.collateral_amount {
+        return Err(ContractError::InvalidInput("Collateral is already posted".to_string()));
+    }
 
-    // Validate funds sent in uxion cover the declared `amount`
-    let sent = info.funds.iter().find(|c| c.denom == "uxion").map(|c| c.amount).unwrap_or_default();
-    if sent < amount {
-        return Err(ContractError::InvalidInput("Insufficient funds sent".to_string()));
+    let excess = escrow_collateral(deps.storage, &mut plan, &member, deposit)?;
+    PLANS.save(deps.storage, plan.id, &plan)?;
+
+    let mut resp = Response::new();
+    if !excess.is_zero() {
+        resp = resp.add_message(send_funds(&plan, &member, excess)?);
+    }
+    Ok(resp
+        .add_attribute("action", "deposit_collateral")
+        .add_attribute("plan_id", plan.id.to_string())
+        .add_attribute("member", member)
+        .add_attribute("collateral", plan.collateral_amount.to_string()))
+}
+
+fn execute_waive_trust_requirement(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+    member: String,
+) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let member = deps.api.addr_validate(&member)?;
+
+    ensure_recruiting(&plan)?;
+    if !plan.participants.contains(&info.sender.to_string()) {
+        return Err(ContractError::NotParticipant {});
+    }
+    let key = (plan_id, member.clone());
+    let mut votes = TRUST_WAIVERS.may_load(deps.storage, key.clone())?.unwrap_or_default();
+    if votes.contains(&info.sender) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+    votes.push(info.sender.clone());
+    TRUST_WAIVERS.save(deps.storage, key, &votes)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "waive_trust_requirement")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("member", member.to_string())
+        .add_attribute("waived", trust_waived(deps.storage, &plan, &member)?.to_string()))
+}
+
+// The member's own score plus the trust their guarantors pledged in this plan
+fn effective_trust_score(storage: &dyn Storage, plan_id: u64, member: &Addr, now: u64) -> StdResult<(u64, u64)> {
+    let score = trust::score(storage, member, now)?;
+    let pledged = GUARANTEES
+        .prefix((plan_id, member.clone()))
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(0u64, |acc, item| item.map(|(_, pledge)| acc + pledge.trust))?;
+    Ok((score, pledged))
+}
+
+// Every current participant voted to let `member` in
+fn trust_waived(storage: &dyn Storage, plan: &Plan, member: &Addr) -> StdResult<bool> {
+    let votes = TRUST_WAIVERS.may_load(storage, (plan.id, member.clone()))?.unwrap_or_default();
+    Ok(plan
+        .participants
+        .iter()
+        .all(|participant| votes.iter().any(|voter| voter.as_str() == participant)))
+}
+
+fn ensure_trusted(storage: &dyn Storage, plan: &Plan, member: &Addr, now: u64) -> Result<(), ContractError> {
+    let (score, pledged) = effective_trust_score(storage, plan.id, member, now)?;
+    if score + pledged < plan.trust_score_required && !trust_waived(storage, plan, member)? {
+        return Err(ContractError::InsufficientTrustScore {});
+    }
+    Ok(())
+}
+
+fn execute_guarantee(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_id: u64,
+    member: String,
+    trust_pledge: Option<u64>,
+) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let deposit = funds_in(&info, &plan.denom)?;
+    guarantee(deps, env, info.sender, plan, member, trust_pledge, deposit)
+}
+
+// Adds to `guarantor`'s pledge for `member`. Guarantors must themselves meet
+// the plan's trust requirement and cannot have more trust pledged, across all
+// their guarantees, than they hold.
+fn guarantee(
+    deps: DepsMut,
+    env: Env,
+    guarantor: Addr,
+    mut plan: Plan,
+    member: String,
+    trust_pledge: Option<u64>,
+    deposit: Uint128,
+) -> Result<Response, ContractError> {
+    let plan_id = plan.id;
+    if plan.status.is_finished() {
+        return Err(ContractError::PlanFinished { status: plan.status.to_string() });
+    }
+    let member = deps.api.addr_validate(&member)?;
+    if member == guarantor {
+        return Err(ContractError::InvalidInput("Members cannot guarantee themselves".to_string()));
+    }
+    let trust = trust_pledge.unwrap_or_default();
+    if trust == 0 && deposit.is_zero() {
+        return Err(ContractError::InvalidInput("Pledge funds or trust".to_string()));
+    }
+
+    let score = trust::score(deps.storage, &guarantor, env.block.time.seconds())?;
+    if score < plan.trust_score_required {
+        return Err(ContractError::InsufficientTrustScore {});
     }
+    let key = (plan_id, member.clone(), guarantor.clone());
+    let mut pledge = GUARANTEES.may_load(deps.storage, key.clone())?.unwrap_or(Guarantee {
+        guarantor: guarantor.clone(),
+        member: member.clone(),
+        funds: Uint128::zero(),
+        trust: 0,
+    });
+    let pledged = PLEDGED_TRUST.may_load(deps.storage, &guarantor)?.unwrap_or_default();
+    if pledged + trust > score {
+        return Err(ContractError::InvalidInput("Cannot pledge more trust than you have".to_string()));
+    }
+    pledge.funds += deposit;
+    pledge.trust += trust;
+    GUARANTEES.save(deps.storage, key, &pledge)?;
+    PLEDGED_TRUST.save(deps.storage, &guarantor, &(pledged + trust))?;
+    plan.balance += deposit;
+    PLANS.save(deps.storage, plan_id, &plan)?;
 
-    // Must have a personal start time (set when approved)
-    let start_opt = PARTICIPANT_START.may_load(deps.storage, (plan_id, sender.clone()))?;
-    let start = start_opt.ok_or_else(|| ContractError::InvalidInput("participant not started".into()))?;
+    Ok(Response::new()
+        .add_attribute("action", "guarantee")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("guarantor", guarantor)
+        .add_attribute("member", member)
+        .add_attribute("funds", pledge.funds.to_string())
+        .add_attribute("trust", pledge.trust.to_string()))
+}
+
+fn execute_withdraw_guarantee(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+    member: String,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+    let member = deps.api.addr_validate(&member)?;
 
-    // Current personal cycle
-    let frequency_str = match &plan.frequency {
-        Frequency::Daily => "Daily",
-        Frequency::Weekly => "Weekly",
-        Frequency::Monthly => "Monthly",
+    // Pledges back a member for as long as they are in a running plan
+    if plan.participants.contains(&member.to_string()) && !plan.status.is_finished() {
+        return Err(ContractError::InvalidInput("Guarantee is locked until the plan ends".to_string()));
+    }
+    let key = (plan_id, member.clone(), info.sender.clone());
+    let Some(pledge) = GUARANTEES.may_load(deps.storage, key.clone())? else {
+        return Err(ContractError::NothingToClaim {});
     };
-    let cycle = current_cycle_for_participant(start, &env, frequency_str);
+    GUARANTEES.remove(deps.storage, key);
+    release_pledged_trust(deps.storage, &info.sender, pledge.trust)?;
 
-    // If first top-up in this cycle, roll last cycle's unpaid portion into debt
-    let already = CONTRIBUTIONS
-        .may_load(deps.storage, (plan_id, sender.clone(), cycle))?
-        .unwrap_or_default();
+    plan.balance = plan
+        .balance
+        .checked_sub(pledge.funds)
+        .map_err(|_| ContractError::InvalidInput("underflow".into()))?;
+    PLANS.save(deps.storage, plan_id, &plan)?;
 
-    if cycle > 0 && already.is_zero() {
-        // Look at previous cycle
-        let prev_cycle = cycle - 1;
-        let prev_paid = CONTRIBUTIONS
-            .may_load(deps.storage, (plan_id, sender.clone(), prev_cycle))?
-            .unwrap_or_default();
-        let prev_debt = plan.contribution_amount.saturating_sub(prev_paid);
-        if !prev_debt.is_zero() {
-            let existing = USER_DEBT
-                .may_load(deps.storage, (plan_id, sender.clone()))?
-                .unwrap_or_default();
-            USER_DEBT.save(deps.storage, (plan_id, sender.clone()), &(existing + prev_debt))?;
-        }
+    let mut resp = Response::new();
+    if !pledge.funds.is_zero() {
+        resp = resp.add_message(send_funds(&plan, &info.sender, pledge.funds)?);
     }
+    Ok(resp
+        .add_attribute("action", "withdraw_guarantee")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("guarantor", info.sender)
+        .add_attribute("member", member)
+        .add_attribute("amount", pledge.funds.to_string()))
+}
 
-    // Debt carried from earlier cycles (does NOT include this cycle’s required amount)
-    let debt = USER_DEBT
-        .may_load(deps.storage, (plan_id, sender.clone()))?
-        .unwrap_or_default();
+fn execute_withdraw_collateral(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+    let member = info.sender;
 
-    // Target for THIS cycle = normal + prior debt
-    let required_this_cycle = plan.contribution_amount + debt;
+    // Members stay bound until the plan ends
+    if plan.participants.contains(&member.to_string()) && !plan.status.is_finished() {
+        return Err(ContractError::InvalidInput("Collateral is locked until the plan ends".to_string()));
+    }
+    let key = (plan_id, member.clone());
+    let amount = COLLATERAL.may_load(deps.storage, key.clone())?.unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    COLLATERAL.remove(deps.storage, key.clone());
+    JOIN_REQUESTS.remove(deps.storage, key);
 
-    // If we already reached or exceeded target, block further payment
-    if already >= required_this_cycle {
-        return Err(ContractError::InvalidInput("Already fully contributed this cycle".to_string()));
+    plan.balance = plan
+        .balance
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InvalidInput("underflow".into()))?;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    Ok(Response::new()
+        .add_message(send_funds(&plan, &member, amount)?)
+        .add_attribute("action", "withdraw_collateral")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("member", member)
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_start_plan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_id: u64,
+    start_at: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+
+    if info.sender != plan.created_by {
+        return Err(ContractError::Unauthorized("Only the plan creator can start the plan".to_string()));
+    }
+    ensure_recruiting(&plan)?;
+    if plan.participants.len() < 2 {
+        return Err(ContractError::NotEnoughParticipants { min: 2 });
     }
 
-    // If partials not allowed, you must pay the exact remaining now
-    let remaining = required_this_cycle.checked_sub(already).unwrap_or_default();
-    if !plan.allow_partial && amount != remaining {
-        return Err(ContractError::InvalidInput("Must contribute exact amount".to_string()));
+    let now = env.block.time.seconds();
+    let start_at = start_at.unwrap_or(now);
+    if start_at < now {
+        return Err(ContractError::InvalidInput("start_at is in the past".to_string()));
+    }
+    // Membership is locked from now on, so don't let the creator park the
+    // plan indefinitely
+    if start_at > calendar::cycle_start(&plan.frequency, now, 1) {
+        return Err(ContractError::InvalidInput("start_at is more than one cycle away".to_string()));
     }
 
-    // Do not allow over-payment beyond this cycle target
-    if amount > remaining {
-        return Err(ContractError::InvalidInput("Contribution exceeds remaining for this cycle".to_string()));
+    start_plan(deps.storage, &env, &mut plan, start_at)?;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_plan")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("start_time", start_at.to_string())
+        .add_attribute("participants", plan.participants.len().to_string())
+        .add_attribute("status", plan.status.to_string()))
+}
+
+fn execute_commit_secret(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_id: u64,
+    commitment: String,
+) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let draw = load_draw(deps.storage, &plan)?;
+
+    if !plan.participants.contains(&info.sender.to_string()) {
+        return Err(ContractError::NotParticipant {});
     }
+    if env.block.time.seconds() >= draw.commit_deadline {
+        return Err(ContractError::DrawPhaseNotOpen { phase: "commit".to_string() });
+    }
+    let key = (plan_id, info.sender.clone());
+    if DRAW_COMMITS.has(deps.storage, key.clone()) {
+        return Err(ContractError::AlreadyCommitted {});
+    }
+    let commitment = commitment.to_lowercase();
+    if commitment.len() != 64 || !commitment.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidInput("Commitment must be a hex sha256 digest".to_string()));
+    }
+    DRAW_COMMITS.save(deps.storage, key, &commitment)?;
 
-    // --- Accumulate this cycle’s contribution ---
-    let new_total = already + amount;
+    Ok(Response::new()
+        .add_attribute("action", "commit_secret")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("participant", info.sender))
+}
+
+fn execute_reveal_secret(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_id: u64,
+    secret: String,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+    let draw = load_draw(deps.storage, &plan)?;
+    let now = env.block.time.seconds();
+
+    // Reveals open once every member has committed or the commit window ends
+    let commits = count_entries(deps.storage, &DRAW_COMMITS, plan_id)?;
+    let all_committed = commits == plan.participants.len();
+    if (now < draw.commit_deadline && !all_committed) || now >= draw.reveal_deadline {
+        return Err(ContractError::DrawPhaseNotOpen { phase: "reveal".to_string() });
+    }
+
+    let key = (plan_id, info.sender.clone());
+    let commitment = DRAW_COMMITS
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::CommitmentMismatch {})?;
+    if DRAW_REVEALS.has(deps.storage, key.clone()) {
+        return Err(ContractError::AlreadyRevealed {});
+    }
+    if schedule::commitment(&secret) != commitment {
+        return Err(ContractError::CommitmentMismatch {});
+    }
+    DRAW_REVEALS.save(deps.storage, key, &secret)?;
+
+    // The last reveal finishes the draw straight away
+    let reveals = count_entries(deps.storage, &DRAW_REVEALS, plan_id)?;
+    if all_committed && reveals == plan.participants.len() {
+        finish_draw(deps.storage, now, &mut plan)?;
+        PLANS.save(deps.storage, plan_id, &plan)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "reveal_secret")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("participant", info.sender)
+        .add_attribute("status", plan.status.to_string()))
+}
+
+fn execute_finalize_draw(
+    deps: DepsMut,
+    env: Env,
+    plan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+    let draw = load_draw(deps.storage, &plan)?;
+    let now = env.block.time.seconds();
+
+    if now < draw.reveal_deadline {
+        return Err(ContractError::DrawPhaseNotOpen { phase: "finalize".to_string() });
+    }
+    finish_draw(deps.storage, now, &mut plan)?;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "finalize_draw")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("start_time", plan.start_time.unwrap_or_default().to_string())
+        .add_attribute("status", plan.status.to_string()))
+}
+
+fn execute_place_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_id: u64,
+    discount: Uint128,
+) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+
+    ensure_active(&plan)?;
+    if plan.payout_mode != PayoutMode::Auction {
+        return Err(ContractError::InvalidInput("Plan does not auction its payouts".to_string()));
+    }
+    if !plan.participants.contains(&info.sender.to_string()) {
+        return Err(ContractError::NotParticipant {});
+    }
+
+    // Bids are only taken while the cycle is still running
+    let cycle = plan.current_cycle as u64;
+    if started_cycle(&plan, env.block.time.seconds()) != Some(cycle) {
+        return Err(ContractError::InvalidInput("No auction is open for this cycle".to_string()));
+    }
+    if !auction_eligible(deps.storage, &plan)?.contains(&info.sender.to_string()) {
+        return Err(ContractError::InvalidInput("You have already won a payout this round".to_string()));
+    }
+    let full_pot = plan.contribution_amount * Uint128::from(plan.participants.len() as u128);
+    if discount >= full_pot {
+        return Err(ContractError::InvalidInput("Discount must be smaller than the pot".to_string()));
+    }
+
+    BIDS.save(deps.storage, (plan_id, cycle, info.sender.clone()), &discount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "place_bid")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("cycle", cycle.to_string())
+        .add_attribute("bidder", info.sender)
+        .add_attribute("discount", discount.to_string()))
+}
+
+fn execute_close_auction(
+    deps: DepsMut,
+    env: Env,
+    plan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+
+    ensure_active(&plan)?;
+    if plan.payout_mode != PayoutMode::Auction {
+        return Err(ContractError::InvalidInput("Plan does not auction its payouts".to_string()));
+    }
+
+    let now = env.block.time.seconds();
+    let closing = plan.current_cycle;
+    let mut payouts = settle_cycles(deps.storage, deps.api, now, &mut plan)?;
+
+    // Nothing had expired: close the running cycle if everyone has paid in
+    if plan.current_cycle == closing {
+        let cycle = closing as u64;
+        if started_cycle(&plan, now) != Some(cycle) || !cycle_funded(deps.storage, &plan, cycle)? {
+            return Err(ContractError::InsufficientContributions {});
+        }
+        payouts.extend(close_cycle(deps.storage, deps.api, now, &mut plan)?);
+    }
+    PLANS.save(deps.storage, plan_id, &plan)?;
+    let badges = mint_badges(deps.storage, &plan)?;
+
+    Ok(Response::new()
+        .add_messages(payouts)
+        .add_submessages(badges)
+        .add_attribute("action", "close_auction")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("closed_cycles", (plan.current_cycle - closing).to_string()))
+}
+
+fn execute_set_payout_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+    order: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+
+    if info.sender != plan.created_by {
+        return Err(ContractError::Unauthorized("Only the plan creator can set the payout order".to_string()));
+    }
+    ensure_recruiting(&plan)?;
+    if plan.payout_order != PayoutOrder::Fixed {
+        return Err(ContractError::InvalidInput("Plan does not use a fixed payout order".to_string()));
+    }
+
+    let mut schedule: Vec<String> = Vec::with_capacity(order.len());
+    for member in order {
+        let addr = deps.api.addr_validate(&member)?.to_string();
+        if !plan.participants.contains(&addr) {
+            return Err(ContractError::InvalidInput(format!("{} is not a participant", addr)));
+        }
+        if schedule.contains(&addr) {
+            return Err(ContractError::InvalidInput(format!("{} is listed twice", addr)));
+        }
+        schedule.push(addr);
+    }
+    plan.payout_schedule = schedule;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_payout_order")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("positions", plan.payout_schedule.len().to_string()))
+}
+
+fn execute_cancel_plan(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+    let mut votes = CANCEL_VOTES.may_load(deps.storage, plan_id)?.unwrap_or_default();
+
+    match plan.status {
+        PlanStatus::Recruiting | PlanStatus::Drawing => {
+            if info.sender != plan.created_by {
+                return Err(ContractError::Unauthorized("Only the plan creator can cancel before the start".to_string()));
+            }
+        }
+        PlanStatus::Active => {
+            if !plan.participants.contains(&info.sender.to_string()) {
+                return Err(ContractError::NotParticipant {});
+            }
+            if votes.contains(&info.sender) {
+                return Err(ContractError::AlreadyVoted {});
+            }
+            votes.push(info.sender.clone());
+            CANCEL_VOTES.save(deps.storage, plan_id, &votes)?;
+        }
+        _ => return Err(ContractError::PlanFinished { status: plan.status.to_string() }),
+    }
+
+    // Once active, more than half of the participants must agree
+    let cancelled = plan.status != PlanStatus::Active
+        || votes.len() * 2 > plan.participants.len();
+    if cancelled {
+        transition(&mut plan, PlanStatus::Cancelled)?;
+        open_refunds(deps.storage, &plan)?;
+        PLANS.save(deps.storage, plan_id, &plan)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_plan")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("votes", votes.len().to_string())
+        .add_attribute("status", plan.status.to_string()))
+}
+
+// Splits what is left in the plan between members according to what they
+// put in minus what they already received (payouts and auction dividends).
+// Members who were paid out more than they contributed get nothing; if the
+// pot cannot cover every claim in full, it is shared pro-rata. Unused credit
+// is reserved for its owner and returned on top by ClaimRefund.
+fn open_refunds(storage: &mut dyn Storage, plan: &Plan) -> Result<(), ContractError> {
+    let mut received: Vec<(Addr, Uint128)> = Vec::new();
+    for item in PAYOUTS.prefix(plan.id).range(storage, None, None, Order::Ascending) {
+        let (_, payout) = item?;
+        match received.iter_mut().find(|(addr, _)| *addr == payout.recipient) {
+            Some((_, total)) => *total += payout.amount,
+            None => received.push((payout.recipient, payout.amount)),
+        }
+    }
+
+    let mut owed: Vec<(Addr, Uint128)> = Vec::new();
+    // Unused credit, unclaimed payouts and the keeper reserve already belong
+    // to their owners
+    let mut reserved = plan.keeper_reserve + PENDING_PAYOUTS
+        .prefix(plan.id)
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |acc, item| item.map(|(_, amount)| acc + amount))?;
+    // Collateral and guarantees are returned separately
+    reserved += COLLATERAL
+        .prefix(plan.id)
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |acc, item| item.map(|(_, amount)| acc + amount))?;
+    reserved += GUARANTEES
+        .sub_prefix(plan.id)
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |acc, item| item.map(|(_, pledge)| acc + pledge.funds))?;
+    for member in &plan.participants {
+        let addr = Addr::unchecked(member);
+        let contributed = CONTRIBUTIONS
+            .prefix((plan.id, addr.clone()))
+            .range(storage, None, None, Order::Ascending)
+            .try_fold(Uint128::zero(), |acc, item| item.map(|(_, amount)| acc + amount))?;
+        let paid_out = received
+            .iter()
+            .find(|(recipient, _)| *recipient == addr)
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default();
+        let dividends = DIVIDENDS.may_load(storage, (plan.id, addr.clone()))?.unwrap_or_default();
+        reserved += CREDITS.may_load(storage, (plan.id, addr.clone()))?.unwrap_or_default();
+
+        let net = contributed.saturating_sub(paid_out + dividends);
+        if !net.is_zero() {
+            owed.push((addr, net));
+        }
+    }
+
+    let total_owed = owed.iter().fold(Uint128::zero(), |acc, (_, net)| acc + net);
+    if total_owed.is_zero() {
+        return Ok(());
+    }
+    let pool = plan.balance.saturating_sub(reserved).min(total_owed);
+    for (addr, net) in owed {
+        let refund = net.multiply_ratio(pool, total_owed);
+        if !refund.is_zero() {
+            REFUNDS.save(storage, (plan.id, addr), &refund)?;
+        }
+    }
+    Ok(())
+}
+
+fn execute_claim_refund(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+    if !plan.status.is_finished() {
+        return Err(ContractError::InvalidInput("Refunds are only available once a plan has ended".to_string()));
+    }
+
+    // Cancellation refund plus any credit the member never got to use; the
+    // creator also takes back the unspent keeper reserve
+    let key = (plan_id, info.sender.clone());
+    let mut refund = REFUNDS.may_load(deps.storage, key.clone())?.unwrap_or_default()
+        + CREDITS.may_load(deps.storage, key.clone())?.unwrap_or_default();
+    if info.sender == plan.created_by {
+        refund += plan.keeper_reserve;
+        plan.keeper_reserve = Uint128::zero();
+    }
+    if refund.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    REFUNDS.remove(deps.storage, key.clone());
+    CREDITS.remove(deps.storage, key);
+
+    plan.balance = plan
+        .balance
+        .checked_sub(refund)
+        .map_err(|_| ContractError::InvalidInput("underflow".into()))?;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    Ok(Response::new()
+        .add_message(send_funds(&plan, &info.sender, refund)?)
+        .add_attribute("action", "claim_refund")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("participant", info.sender)
+        .add_attribute("amount", refund.to_string()))
+}
+
+fn execute_claim_payout(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+
+    let key = (plan_id, info.sender.clone());
+    let amount = PENDING_PAYOUTS.may_load(deps.storage, key.clone())?.unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    PENDING_PAYOUTS.remove(deps.storage, key);
+
+    plan.balance = plan
+        .balance
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InvalidInput("underflow".into()))?;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    Ok(Response::new()
+        .add_message(send_funds(&plan, &info.sender, amount)?)
+        .add_attribute("action", "claim_payout")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("recipient", info.sender)
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_advance_cycle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_id: u64,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+    ensure_active(&plan)?;
+
+    // Only cycles whose deadline has passed; funded cycles already close
+    // with the contribution that completes them
+    let now = env.block.time.seconds();
+    let closing = plan.current_cycle;
+    if started_cycle(&plan, now).is_none_or(|cycle| cycle <= closing as u64) {
+        return Err(ContractError::CycleNotDue {});
+    }
+    let mut payouts = settle_cycles(deps.storage, deps.api, now, &mut plan)?;
+
+    let tip = plan.keeper_tip.min(plan.keeper_reserve);
+    if !tip.is_zero() {
+        plan.keeper_reserve -= tip;
+        plan.balance = plan
+            .balance
+            .checked_sub(tip)
+            .map_err(|_| ContractError::InvalidInput("underflow".into()))?;
+        payouts.push(send_funds(&plan, &info.sender, tip)?);
+    }
+    PLANS.save(deps.storage, plan_id, &plan)?;
+    let badges = mint_badges(deps.storage, &plan)?;
+
+    Ok(Response::new()
+        .add_messages(payouts)
+        .add_submessages(badges)
+        .add_attribute("action", "advance_cycle")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("keeper", info.sender)
+        .add_attribute("closed_cycles", (plan.current_cycle - closing).to_string())
+        .add_attribute("keeper_tip", tip.to_string()))
+}
+
+fn execute_fund_keeper_reserve(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let sent = funds_in(&info, &plan.denom)?;
+    fund_keeper_reserve(deps, info.sender, plan, sent)
+}
+
+// The leftover reserve is refunded to the creator, so only they may fund it
+fn fund_keeper_reserve(deps: DepsMut, sender: Addr, mut plan: Plan, sent: Uint128) -> Result<Response, ContractError> {
+    let plan_id = plan.id;
+    if sender != plan.created_by {
+        return Err(ContractError::Unauthorized("Only the plan creator can fund the keeper reserve".to_string()));
+    }
+    if plan.status.is_finished() {
+        return Err(ContractError::PlanFinished { status: plan.status.to_string() });
+    }
+    if sent.is_zero() {
+        return Err(ContractError::InvalidInput("No funds sent".to_string()));
+    }
+    plan.keeper_reserve += sent;
+    plan.balance += sent;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_keeper_reserve")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("amount", sent.to_string())
+        .add_attribute("keeper_reserve", plan.keeper_reserve.to_string()))
+}
+
+fn execute_update_allowed_denoms(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<UncheckedDenom>,
+    remove: Vec<Denom>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized("Only the admin can change allowed denoms".to_string()));
+    }
+
+    config.allowed_denoms.retain(|denom| !remove.contains(denom));
+    for denom in add {
+        let denom = check_denom(deps.as_ref(), denom)?;
+        if !config.allowed_denoms.contains(&denom) {
+            config.allowed_denoms.push(denom);
+        }
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_allowed_denoms")
+        .add_attribute(
+            "allowed_denoms",
+            config.allowed_denoms.iter().map(denom_label).collect::<Vec<_>>().join(","),
+        ))
+}
+
+fn execute_update_insurance_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+    premium_bps: Option<u16>,
+    max_cover_per_cycle: Option<Uint128>,
+    max_cover_per_plan: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized("Only the admin can configure the insurance pool".to_string()));
+    }
+
+    let mut pool = INSURANCE_POOL.load(deps.storage)?;
+    if let Some(bps) = premium_bps {
+        if bps > 10_000 {
+            return Err(ContractError::InvalidInput("Invalid insurance premium".to_string()));
+        }
+        pool.premium_bps = bps;
+    }
+    if let Some(cap) = max_cover_per_cycle {
+        pool.max_cover_per_cycle = cap;
+    }
+    if let Some(cap) = max_cover_per_plan {
+        pool.max_cover_per_plan = cap;
+    }
+    INSURANCE_POOL.save(deps.storage, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_insurance_pool")
+        .add_attribute("premium_bps", pool.premium_bps.to_string())
+        .add_attribute("max_cover_per_cycle", pool.max_cover_per_cycle.to_string())
+        .add_attribute("max_cover_per_plan", pool.max_cover_per_plan.to_string()))
+}
+
+fn execute_set_badge_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized("Only the admin can set the badge contract".to_string()));
+    }
+    config.badge_contract = contract.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_badge_contract")
+        .add_attribute("badge_contract", config.badge_contract.map(String::from).unwrap_or_default()))
+}
+
+fn execute_set_attester(
+    deps: DepsMut,
+    info: MessageInfo,
+    attester: String,
+    weight_bps: Option<u16>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized("Only the admin can register attesters".to_string()));
+    }
+
+    let attester = deps.api.addr_validate(&attester)?;
+    match weight_bps {
+        Some(bps) if bps == 0 || bps > 10_000 => {
+            return Err(ContractError::InvalidInput("Invalid attester weight".to_string()));
+        }
+        Some(bps) => ATTESTERS.save(deps.storage, &attester, &bps)?,
+        None => ATTESTERS.remove(deps.storage, &attester),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_attester")
+        .add_attribute("attester", attester)
+        .add_attribute("weight_bps", weight_bps.unwrap_or(0).to_string()))
+}
+
+fn execute_submit_attestation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    subject: String,
+    delta: i64,
+    evidence_hash: String,
+) -> Result<Response, ContractError> {
+    let Some(weight_bps) = ATTESTERS.may_load(deps.storage, &info.sender)? else {
+        return Err(ContractError::Unauthorized("Only registered attesters can submit attestations".to_string()));
+    };
+    let subject = deps.api.addr_validate(&subject)?;
+    if subject == info.sender {
+        return Err(ContractError::InvalidInput("Attesters cannot attest for themselves".to_string()));
+    }
+    if delta == 0 || delta.unsigned_abs() > reputation::MAX_ATTESTATION_DELTA as u64 {
+        return Err(ContractError::InvalidInput("Invalid attestation delta".to_string()));
+    }
+    if evidence_hash.len() != 64 || !evidence_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidInput("Evidence hash must be a hex sha256 digest".to_string()));
+    }
+    let evidence_hash = evidence_hash.to_ascii_lowercase();
+    if ATTESTATIONS.has(deps.storage, (&info.sender, &evidence_hash)) {
+        return Err(ContractError::DuplicateAttestation { evidence_hash });
+    }
+
+    let now = env.block.time.seconds();
+    let points = reputation::weighted_delta(delta, weight_bps);
+    let attestation = Attestation {
+        attester: info.sender.clone(),
+        subject: subject.clone(),
+        delta,
+        weight_bps,
+        points,
+        at: now,
+    };
+    ATTESTATIONS.save(deps.storage, (&info.sender, &evidence_hash), &attestation)?;
+    let reason = TrustReason::Attestation { attester: info.sender.clone(), evidence_hash: evidence_hash.clone(), points };
+    let score = trust::apply(deps.storage, &subject, now, reason, None, None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_attestation")
+        .add_attribute("attester", info.sender)
+        .add_attribute("subject", subject)
+        .add_attribute("evidence_hash", evidence_hash)
+        .add_attribute("points", points.to_string())
+        .add_attribute("trust_score", score.to_string()))
+}
+
+fn execute_set_auto_push(
+    deps: DepsMut,
+    info: MessageInfo,
+    plan_id: u64,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
+
+    if info.sender != plan.created_by {
+        return Err(ContractError::Unauthorized("Only the plan creator can change payout delivery".to_string()));
+    }
+    if plan.status.is_finished() {
+        return Err(ContractError::PlanFinished { status: plan.status.to_string() });
+    }
+    plan.auto_push = enabled;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_auto_push")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+// Tokens arrive through the CW20 contract; the hook says what they are for
+fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    let hook: ContributeHookMsg = from_json(&wrapper.msg)?;
+    let plan_id = match hook {
+        ContributeHookMsg::Contribute { plan_id, .. }
+        | ContributeHookMsg::ContributeAhead { plan_id, .. }
+        | ContributeHookMsg::FundKeeperReserve { plan_id }
+        | ContributeHookMsg::JoinPlan { plan_id }
+        | ContributeHookMsg::RequestToJoinPlan { plan_id }
+        | ContributeHookMsg::DepositCollateral { plan_id }
+        | ContributeHookMsg::Guarantee { plan_id, .. } => plan_id,
+    };
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    if plan.denom != Denom::Cw20(info.sender.clone()) {
+        return Err(ContractError::WrongDenom {
+            expected: denom_label(&plan.denom),
+            received: info.sender.to_string(),
+        });
+    }
+
+    match hook {
+        ContributeHookMsg::Contribute { amount, .. } => contribute(deps, env, sender, plan, amount, wrapper.amount),
+        ContributeHookMsg::ContributeAhead { cycles, .. } => {
+            contribute_ahead(deps, env, sender, plan, cycles, wrapper.amount)
+        }
+        ContributeHookMsg::FundKeeperReserve { .. } => fund_keeper_reserve(deps, sender, plan, wrapper.amount),
+        ContributeHookMsg::JoinPlan { .. } => join_plan(deps, env, sender, plan, wrapper.amount),
+        ContributeHookMsg::RequestToJoinPlan { .. } => request_to_join_plan(deps, env, sender, plan, wrapper.amount),
+        ContributeHookMsg::DepositCollateral { .. } => deposit_collateral(deps, sender, plan, wrapper.amount),
+        ContributeHookMsg::Guarantee { member, trust_pledge, .. } => {
+            guarantee(deps, env, sender, plan, member, trust_pledge, wrapper.amount)
+        }
+    }
+}
+
+fn execute_contribute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let sent = funds_in(&info, &plan.denom)?;
+    contribute(deps, env, info.sender, plan, amount, sent)
+}
+
+fn contribute(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    mut plan: Plan,
+    amount: Uint128,
+    sent: Uint128,
+) -> Result<Response, ContractError> {
+    let plan_id = plan.id;
+
+    ensure_active(&plan)?;
+    if !plan.participants.contains(&sender.to_string()) {
+        return Err(ContractError::NotParticipant {});
+    }
+
+    // Validate funds sent in the plan's denom cover the declared `amount`
+    if sent < amount {
+        return Err(ContractError::InvalidInput("Insufficient funds sent".to_string()));
+    }
+
+    // Shares are only taken once cycle 0 has opened; ContributeAhead is the
+    // way to pay early
+    let now = env.block.time.seconds();
+    if let Some(start_time) = plan.start_time.filter(|start| now < *start) {
+        return Err(ContractError::PlanNotStarted { start_time });
+    }
+
+    // Close any cycle that has already ended so debts are up to date
+    let mut payouts = settle_cycles(deps.storage, deps.api, now, &mut plan)?;
+    ensure_active(&plan)?;
+
+    // Current plan-wide cycle
+    let cycle = plan_cycle(&plan, now);
+    if cycle < plan.current_cycle as u64 {
+        return Err(ContractError::InvalidInput("This cycle has already been paid out".to_string()));
+    }
+
+    let already = CONTRIBUTIONS
+        .may_load(deps.storage, (plan_id, sender.clone(), cycle))?
+        .unwrap_or_default();
+
+    // Debt carried from closed cycles (does NOT include this cycle’s required amount)
+    let debt = USER_DEBT
+        .may_load(deps.storage, (plan_id, sender.clone()))?
+        .unwrap_or_default();
+
+    // Target for THIS cycle = normal + prior debt
+    let required_this_cycle = plan.contribution_amount + debt;
+
+    // If we already reached or exceeded target, block further payment
+    if already >= required_this_cycle {
+        return Err(ContractError::InvalidInput("Already fully contributed this cycle".to_string()));
+    }
+
+    // If partials not allowed, you must pay the exact remaining now
+    let remaining = required_this_cycle.checked_sub(already).unwrap_or_default();
+    if !plan.allow_partial && amount != remaining {
+        return Err(ContractError::InvalidInput("Must contribute exact amount".to_string()));
+    }
+
+    // Do not allow over-payment beyond this cycle target
+    if amount > remaining {
+        return Err(ContractError::InvalidInput("Contribution exceeds remaining for this cycle".to_string()));
+    }
+
+    // Repaying debt is always late; this cycle's share is late once its
+    // deadline has passed
+    let to_share = amount.min(plan.contribution_amount.saturating_sub(already));
+    let past_deadline = cycle_deadline(&plan, cycle).is_some_and(|deadline| now > deadline);
+    let late_amount = if past_deadline { amount } else { amount - to_share };
+    let fee = late_fee(deps.storage, &plan, &sender, cycle, late_amount)?;
+    let premium = insurance_premium(deps.storage, &plan, amount)?;
+    if sent < amount + fee + premium {
+        return Err(ContractError::InvalidInput(format!(
+            "Insufficient funds sent (late fee {}, insurance premium {})",
+            fee, premium
+        )));
+    }
+
+    // --- Accumulate this cycle’s contribution ---
+    let new_total = already + amount;
+
+	// Missed cycles are penalised when the cycle closes (see `close_cycle`)
+	let trust_reason = if amount == plan.contribution_amount && debt.is_zero() && already.is_zero() {
+		Some(if past_deadline { TrustReason::LateContribution } else { TrustReason::OnTimeContribution })
+	} else if amount < plan.contribution_amount && plan.allow_partial {
+		Some(TrustReason::PartialContribution)
+	} else if !debt.is_zero() && amount > Uint128::zero() {
+		Some(TrustReason::DebtRepayment)
+	} else {
+		None
+	};
+
+	plan.balance += amount;
+    // --- Update debt progressively ---
+    // Payment applies first to this cycle's normal contribution amount.
+    // Any excess beyond `contribution_amount` reduces old `debt`.
+    // extra_applied_to_debt = max(0, new_total - contribution_amount)
+    let extra_applied_to_debt = if new_total > plan.contribution_amount {
+        new_total.checked_sub(plan.contribution_amount).unwrap_or_default()
+    } else {
+        Uint128::zero()
+    };
+    let new_debt = debt.saturating_sub(extra_applied_to_debt);
+
+    // Persist state
+    if let Some(reason) = trust_reason {
+        trust::apply(deps.storage, &sender, now, reason, Some(plan_id), Some(cycle))?;
+    }
+    CONTRIBUTIONS.save(deps.storage, (plan_id, sender.clone(), cycle), &new_total)?;
+    if !past_deadline && already < plan.contribution_amount && new_total >= plan.contribution_amount {
+        ON_TIME_SHARES.save(deps.storage, (plan_id, sender.clone(), cycle), &true)?;
+    }
+    // Repaid debt belongs to the recipients of the cycles it was missing from
+    let repaid = debt - new_debt;
+    CYCLE_POT.update::<_, StdError>(deps.storage, (plan_id, cycle), |pot| {
+        Ok(pot.unwrap_or_default() + amount - repaid)
+    })?;
+    USER_DEBT.save(deps.storage, (plan_id, sender.clone()), &new_debt)?;
+    payouts.extend(repay_debt(deps.storage, &mut plan, &sender, cycle, repaid)?);
+
+    if !fee.is_zero() {
+        LATE_FEES.update::<_, StdError>(deps.storage, (plan_id, sender.clone(), cycle), |charged| {
+            Ok(charged.unwrap_or_default() + fee)
+        })?;
+        match &plan.late_fee_destination {
+            FeeDestination::Pot => {
+                plan.balance += fee;
+                CYCLE_POT.update::<_, StdError>(deps.storage, (plan_id, cycle), |pot| {
+                    Ok(pot.unwrap_or_default() + fee)
+                })?;
+            }
+            FeeDestination::Recipient => {
+                let slot = (plan.current_cycle as usize) % plan.payout_schedule.len();
+                let recipient = Addr::unchecked(&plan.payout_schedule[slot]);
+                if plan.auto_push {
+                    payouts.push(send_funds(&plan, &recipient, fee)?);
+                } else {
+                    // Held for ClaimPayout like the recipient's payout itself
+                    plan.balance += fee;
+                    PENDING_PAYOUTS.update::<_, StdError>(deps.storage, (plan_id, recipient), |pending| {
+                        Ok(pending.unwrap_or_default() + fee)
+                    })?;
+                }
+            }
+            FeeDestination::Treasury { address } => payouts.push(send_funds(&plan, address, fee)?),
+        }
+    }
+
+    add_to_insurance_pool(deps.storage, &plan.denom, premium)?;
+
+    // This contribution may complete the cycle's pot
+    payouts.extend(settle_cycles(deps.storage, deps.api, now, &mut plan)?);
+    PLANS.save(deps.storage, plan_id, &plan)?;
+    let badges = mint_badges(deps.storage, &plan)?;
+
+    // Anything attached beyond `amount`, the fee and the premium goes straight back
+    let excess = sent - amount - fee - premium;
+    if !excess.is_zero() {
+        payouts.push(send_funds(&plan, &sender, excess)?);
+    }
+
+    let mut resp = Response::new()
+        .add_attribute("action", "contribute")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("cycle", cycle.to_string())
+        .add_attribute("from", sender)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("contributed_total_this_cycle", new_total.to_string())
+        .add_attribute("debt_after", new_debt.to_string())
+        .add_attribute("late_fee", fee.to_string())
+        .add_attribute("insurance_premium", premium.to_string())
+        .add_attribute("refunded", excess.to_string())
+        .add_submessages(badges);
+
+    if !payouts.is_empty() {
+        resp = resp
+            .add_messages(payouts)
+            .add_attribute("auto_payout", "true");
+    }
+
+    Ok(resp)
+}
+
+fn execute_contribute_ahead(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_id: u64,
+    cycles: u32,
+) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let sent = funds_in(&info, &plan.denom)?;
+    contribute_ahead(deps, env, info.sender, plan, cycles, sent)
+}
+
+fn contribute_ahead(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    mut plan: Plan,
+    cycles: u32,
+    sent: Uint128,
+) -> Result<Response, ContractError> {
+    let plan_id = plan.id;
+
+    ensure_active(&plan)?;
+    if !plan.participants.contains(&sender.to_string()) {
+        return Err(ContractError::NotParticipant {});
+    }
+    if cycles == 0 {
+        return Err(ContractError::InvalidInput("Prepay at least one cycle".to_string()));
+    }
+    let amount = plan.contribution_amount * Uint128::from(cycles);
+    let premium = insurance_premium(deps.storage, &plan, amount)?;
+    if sent < amount + premium {
+        return Err(ContractError::InvalidInput(format!("Insufficient funds sent (insurance premium {})", premium)));
+    }
+
+    let now = env.block.time.seconds();
+    let mut payouts = settle_cycles(deps.storage, deps.api, now, &mut plan)?;
+    ensure_active(&plan)?;
+
+    // Credit may not outlast the plan: at most one share per cycle still open
+    let key = (plan_id, sender.clone());
+    let credit = CREDITS.may_load(deps.storage, key.clone())?.unwrap_or_default() + amount;
+    let open_cycles = total_cycles(&plan).saturating_sub(plan.current_cycle as u64);
+    if credit > plan.contribution_amount * Uint128::from(open_cycles) {
+        return Err(ContractError::InvalidInput("Cannot prepay past the plan's last cycle".to_string()));
+    }
+    CREDITS.save(deps.storage, key, &credit)?;
+    plan.balance += amount;
+    add_to_insurance_pool(deps.storage, &plan.denom, premium)?;
+
+    // The running cycle takes its share right away
+    payouts.extend(settle_cycles(deps.storage, deps.api, now, &mut plan)?);
+    PLANS.save(deps.storage, plan_id, &plan)?;
+    let badges = mint_badges(deps.storage, &plan)?;
+
+    let excess = sent - amount - premium;
+    if !excess.is_zero() {
+        payouts.push(send_funds(&plan, &sender, excess)?);
+    }
+    let credit = CREDITS.may_load(deps.storage, (plan_id, sender.clone()))?.unwrap_or_default();
+
+    Ok(Response::new()
+        .add_messages(payouts)
+        .add_submessages(badges)
+        .add_attribute("action", "contribute_ahead")
+        .add_attribute("plan_id", plan_id.to_string())
+        .add_attribute("from", sender)
+        .add_attribute("cycles", cycles.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("credit_after", credit.to_string())
+        .add_attribute("insurance_premium", premium.to_string())
+        .add_attribute("refunded", excess.to_string()))
+}
+
+// Shares for `cycle` are due when the grace period after its start runs out
+fn cycle_deadline(plan: &Plan, cycle: u64) -> Option<u64> {
+    let (start, grace) = (plan.start_time?, plan.grace_period_seconds?);
+    Some(calendar::cycle_start(&plan.frequency, start, cycle).saturating_add(grace))
+}
+
+// Fee owed on paying `late_amount` late during `cycle`; a flat fee is only
+// charged once per member per cycle
+fn late_fee(
+    storage: &dyn Storage,
+    plan: &Plan,
+    member: &Addr,
+    cycle: u64,
+    late_amount: Uint128,
+) -> StdResult<Uint128> {
+    if late_amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    Ok(match &plan.late_fee {
+        None => Uint128::zero(),
+        Some(LateFee::Flat { amount }) => {
+            if LATE_FEES.has(storage, (plan.id, member.clone(), cycle)) {
+                Uint128::zero()
+            } else {
+                *amount
+            }
+        }
+        Some(LateFee::Bps { bps }) => late_amount.multiply_ratio(*bps as u128, 10_000u128),
+    })
+}
+
+// Badge mints for the members of a plan that just completed: those still in
+// it who never missed a share. Members who already have a badge are skipped.
+fn mint_badges(storage: &mut dyn Storage, plan: &Plan) -> Result<Vec<SubMsg>, ContractError> {
+    let mut mints = Vec::new();
+    if plan.status != PlanStatus::Completed {
+        return Ok(mints);
+    }
+    let Some(contract) = CONFIG.load(storage)?.badge_contract else {
+        return Ok(mints);
+    };
+
+    let cycles = total_cycles(plan);
+    for member in &plan.participants {
+        let addr = Addr::unchecked(member);
+        let key = (plan.id, addr.clone());
+        if BADGES.has(storage, key.clone())
+            || DEFAULTS.has(storage, key.clone())
+            || MISSED_CYCLES.may_load(storage, key.clone())?.unwrap_or_default() > 0
+        {
+            continue;
+        }
+
+        let on_time = ON_TIME_SHARES
+            .prefix(key.clone())
+            .keys(storage, None, None, Order::Ascending)
+            .count() as u64;
+        let metadata = BadgeMetadata {
+            plan_id: plan.id,
+            cycles_completed: cycles,
+            on_time_bps: (on_time.min(cycles) * 10_000 / cycles.max(1)) as u16,
+        };
+        let badge = Badge {
+            member: addr.clone(),
+            token_id: badge::token_id(plan.id, member),
+            metadata: metadata.clone(),
+            status: BadgeStatus::Pending,
+        };
+        let mint = BadgeExecuteMsg::Mint {
+            token_id: badge.token_id.clone(),
+            owner: member.clone(),
+            token_uri: None,
+            extension: metadata,
+        };
+        BADGES.save(storage, key.clone(), &badge)?;
+        // A failed mint must not undo the plan's completion
+        mints.push(
+            SubMsg::reply_always(wasm_execute(&contract, &mint, vec![])?, badge::BADGE_REPLY_ID)
+                .with_payload(to_json_binary(&key)?),
+        );
+    }
+    Ok(mints)
+}
+
+// Closes every cycle that has ended, plus the current one once every member
+// has paid their share. Returns the payouts to send.
+fn settle_cycles(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    now: u64,
+    plan: &mut Plan,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut payouts = Vec::new();
+    let Some(now_cycle) = started_cycle(plan, now) else {
+        return Ok(payouts);
+    };
+
+    while plan.status == PlanStatus::Active {
+        let cycle = plan.current_cycle as u64;
+        if cycle > now_cycle {
+            break;
+        }
+        apply_credits(storage, plan, cycle)?;
+
+        // Auction pots are only closed early through CloseAuction
+        let due = cycle < now_cycle
+            || (plan.payout_mode == PayoutMode::RoundRobin && cycle_funded(storage, plan, cycle)?);
+        if !due {
+            break;
+        }
+        if let Some(msg) = close_cycle(storage, api, now, plan)? {
+            payouts.push(msg);
+        }
+    }
+    Ok(payouts)
+}
+
+// Current plan cycle, or `None` before the shared start time
+fn started_cycle(plan: &Plan, now: u64) -> Option<u64> {
+    match plan.start_time {
+        Some(start) if now >= start => Some(calendar::cycles_between(&plan.frequency, start, now)),
+        _ => None,
+    }
+}
+
+fn cycle_funded(storage: &dyn Storage, plan: &Plan, cycle: u64) -> StdResult<bool> {
+    for member in &plan.participants {
+        let paid = CONTRIBUTIONS
+            .may_load(storage, (plan.id, Addr::unchecked(member), cycle))?
+            .unwrap_or_default();
+        if paid < plan.contribution_amount {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+// Uses members' credit (e.g. auction dividends) towards their share of
+// `cycle`; a share credit completes counts as paid on time
+fn apply_credits(storage: &mut dyn Storage, plan: &Plan, cycle: u64) -> StdResult<()> {
+    for member in &plan.participants {
+        let addr = Addr::unchecked(member);
+        let credit = CREDITS.may_load(storage, (plan.id, addr.clone()))?.unwrap_or_default();
+        if credit.is_zero() {
+            continue;
+        }
+        let key = (plan.id, addr.clone(), cycle);
+        let paid = CONTRIBUTIONS.may_load(storage, key.clone())?.unwrap_or_default();
+        let used = credit.min(plan.contribution_amount.saturating_sub(paid));
+        if used.is_zero() {
+            continue;
+        }
+        CONTRIBUTIONS.save(storage, key.clone(), &(paid + used))?;
+        if paid + used >= plan.contribution_amount {
+            ON_TIME_SHARES.save(storage, key, &true)?;
+        }
+        CYCLE_POT.update::<_, StdError>(storage, (plan.id, cycle), |pot| {
+            Ok(pot.unwrap_or_default() + used)
+        })?;
+        CREDITS.save(storage, (plan.id, addr), &(credit - used))?;
+    }
+    Ok(())
+}
+
+// Draws on `member`'s guarantees for a missed share of `cycle`: pledged funds
+// (already in the plan's balance) move into the pot in pledge order, and
+// each guarantor drawn on loses their pledged trust, until the shortfall is
+// covered. Returns the amount covered.
+fn draw_guarantees(
+    storage: &mut dyn Storage,
+    now: u64,
+    plan: &Plan,
+    cycle: u64,
+    member: &Addr,
+    shortfall: Uint128,
+) -> StdResult<Uint128> {
+    let pledges: Vec<Guarantee> = GUARANTEES
+        .prefix((plan.id, member.clone()))
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, pledge)| pledge))
+        .collect::<StdResult<_>>()?;
+
+    let mut covered = Uint128::zero();
+    for mut pledge in pledges {
+        if covered == shortfall {
+            break;
+        }
+        let drawn = pledge.funds.min(shortfall - covered);
+        if drawn.is_zero() && pledge.trust == 0 {
+            continue;
+        }
+        pledge.funds -= drawn;
+        covered += drawn;
+        if pledge.trust > 0 {
+            let reason = TrustReason::GuaranteeDrawn { pledged: pledge.trust };
+            trust::apply(storage, &pledge.guarantor, now, reason, Some(plan.id), Some(cycle))?;
+            release_pledged_trust(storage, &pledge.guarantor, pledge.trust)?;
+            pledge.trust = 0;
+        }
+        GUARANTEES.save(storage, (plan.id, member.clone(), pledge.guarantor.clone()), &pledge)?;
+    }
+    if !covered.is_zero() {
+        CYCLE_POT.update::<_, StdError>(storage, (plan.id, cycle), |pot| {
+            Ok(pot.unwrap_or_default() + covered)
+        })?;
+    }
+    Ok(covered)
+}
+
+// Frees trust a guarantor had pledged once it is drawn or withdrawn
+fn release_pledged_trust(storage: &mut dyn Storage, guarantor: &Addr, trust: u64) -> StdResult<()> {
+    if trust == 0 {
+        return Ok(());
+    }
+    let pledged = PLEDGED_TRUST.may_load(storage, guarantor)?.unwrap_or_default();
+    PLEDGED_TRUST.save(storage, guarantor, &pledged.saturating_sub(trust))
+}
+
+// Premium owed on `amount` paid into an insured plan
+fn insurance_premium(storage: &dyn Storage, plan: &Plan, amount: Uint128) -> StdResult<Uint128> {
+    if !plan.insured {
+        return Ok(Uint128::zero());
+    }
+    let pool = INSURANCE_POOL.load(storage)?;
+    Ok(amount.multiply_ratio(pool.premium_bps as u128, 10_000u128))
+}
+
+fn add_to_insurance_pool(storage: &mut dyn Storage, denom: &Denom, amount: Uint128) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let mut pool = INSURANCE_POOL.load(storage)?;
+    match pool.reserves.iter_mut().find(|reserve| reserve.denom == *denom) {
+        Some(reserve) => reserve.amount += amount,
+        None => pool.reserves.push(PoolReserve { denom: denom.clone(), amount }),
+    }
+    INSURANCE_POOL.save(storage, &pool)
+}
+
+// Covers as much of `member`'s missed share of `cycle` as the reserve and the
+// pool's caps allow, moving it into the cycle's pot. Returns the amount
+// covered; the caller adds it to the plan's balance.
+fn insurance_cover(
+    storage: &mut dyn Storage,
+    plan: &Plan,
+    cycle: u64,
+    member: &Addr,
+    shortfall: Uint128,
+) -> StdResult<Uint128> {
+    let mut pool = INSURANCE_POOL.load(storage)?;
+    let Some(reserve) = pool.reserves.iter_mut().find(|reserve| reserve.denom == plan.denom) else {
+        return Ok(Uint128::zero());
+    };
+    let mut claim = INSURANCE_CLAIMS
+        .may_load(storage, (plan.id, cycle))?
+        .unwrap_or(InsuranceClaim { cycle, amount: Uint128::zero(), members: vec![] });
+    let claimed = insurance_claimed(storage, plan.id)?;
+
+    let covered = shortfall
+        .min(reserve.amount)
+        .min(pool.max_cover_per_cycle.saturating_sub(claim.amount))
+        .min(pool.max_cover_per_plan.saturating_sub(claimed));
+    if covered.is_zero() {
+        return Ok(covered);
+    }
+    reserve.amount -= covered;
+    INSURANCE_POOL.save(storage, &pool)?;
+
+    claim.amount += covered;
+    claim.members.push(member.clone());
+    INSURANCE_CLAIMS.save(storage, (plan.id, cycle), &claim)?;
+    CYCLE_POT.update::<_, StdError>(storage, (plan.id, cycle), |pot| {
+        Ok(pot.unwrap_or_default() + covered)
+    })?;
+    Ok(covered)
+}
+
+// Everything the insurance pool has paid into `plan_id`
+fn insurance_claimed(storage: &dyn Storage, plan_id: u64) -> StdResult<Uint128> {
+    INSURANCE_CLAIMS
+        .prefix(plan_id)
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |acc, item| item.map(|(_, claim)| acc + claim.amount))
+}
+
+// Closes `plan.current_cycle`: unpaid shares roll into USER_DEBT, members who
+// paid nothing are penalised, members past the missed-cycle limit are
+// ejected, and the cycle's pot goes to the next recipient (or, in auction
+// mode, to the winning bidder minus their discount).
+fn close_cycle(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    now: u64,
+    plan: &mut Plan,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let cycle = plan.current_cycle as u64;
+    let mut defaulters = Vec::new();
+    let mut insured = Uint128::zero();
+
+    for member in &plan.participants {
+        let addr = Addr::unchecked(member);
+        let paid = CONTRIBUTIONS
+            .may_load(storage, (plan.id, addr.clone(), cycle))?
+            .unwrap_or_default();
+
+        let mut shortfall = plan.contribution_amount.saturating_sub(paid);
+
+        // Collateral covers a missed share before it turns into debt
+        let held = COLLATERAL.may_load(storage, (plan.id, addr.clone()))?.unwrap_or_default();
+        let seized = held.min(shortfall);
+        if !seized.is_zero() {
+            COLLATERAL.save(storage, (plan.id, addr.clone()), &(held - seized))?;
+            CONTRIBUTIONS.save(storage, (plan.id, addr.clone(), cycle), &(paid + seized))?;
+            CYCLE_POT.update::<_, StdError>(storage, (plan.id, cycle), |pot| {
+                Ok(pot.unwrap_or_default() + seized)
+            })?;
+            shortfall -= seized;
+        }
+        // Then the member's guarantors
+        if !shortfall.is_zero() {
+            shortfall -= draw_guarantees(storage, now, plan, cycle, &addr, shortfall)?;
+        }
+        // Then the insurance pool, for plans that pay its premium
+        if !shortfall.is_zero() && plan.insured {
+            let covered = insurance_cover(storage, plan, cycle, &addr, shortfall)?;
+            insured += covered;
+            shortfall -= covered;
+        }
+
+        if !shortfall.is_zero() {
+            let debt = USER_DEBT.may_load(storage, (plan.id, addr.clone()))?.unwrap_or_default();
+            USER_DEBT.save(storage, (plan.id, addr.clone()), &(debt + shortfall))?;
+            DEBT_OWED.save(storage, (plan.id, addr.clone(), cycle), &shortfall)?;
+        }
+        if paid.is_zero() {
+            trust::apply(storage, &addr, now, TrustReason::MissedCycle, Some(plan.id), Some(cycle))?;
+        }
+
+        if paid < plan.contribution_amount {
+            let missed = MISSED_CYCLES.may_load(storage, (plan.id, addr.clone()))?.unwrap_or_default() + 1;
+            MISSED_CYCLES.save(storage, (plan.id, addr.clone()), &missed)?;
+            if plan.max_missed_cycles.is_some_and(|limit| missed >= limit) {
+                defaulters.push((addr, missed));
+            }
+        }
+    }
 
-	let mut trust_score = TRUST_SCORE
-		.may_load(deps.storage, &sender)?
-		.unwrap_or(50);
-	
-	// Case 1: full payment at once
-	if amount == plan.contribution_amount && debt.is_zero() && already.is_zero() {
-		trust_score += 10;
-	}
-
-	// Case 2: partial payment allowed
-	else if amount < plan.contribution_amount && plan.allow_partial {
-		trust_score += 5;
-	}
-
-	// Case 3: late payment (previous cycle unpaid but now covering it)
-	else if !debt.is_zero() && amount > Uint128::zero() {
-		trust_score += 4;
-	}
-
-	// Case 4: missed cycle entirely (already handled above, mark penalty here)
-	if cycle > 0 {
-		let prev_cycle = cycle - 1;
-		let prev_paid = CONTRIBUTIONS
-			.may_load(deps.storage, (plan_id, sender.clone(), prev_cycle))?
-			.unwrap_or_default();
-		if prev_paid.is_zero() {
-			trust_score -= 15;
-		}
-	}
+    plan.balance += insured;
 
+    for (addr, missed) in defaulters {
+        eject_defaulter(storage, now, plan, &addr, cycle, missed)?;
+    }
+    // Nobody left to pay out to: the open cycle is unwound and what remains
+    // is refunded
+    if plan.participants.len() < 2 || total_cycles(plan) <= cycle {
+        let status = if plan.participants.len() < 2 { PlanStatus::Dissolved } else { PlanStatus::Completed };
+        wind_down(storage, plan, cycle, status)?;
+        return Ok(None);
+    }
 
-	plan.balance += amount;
-    // --- Update debt progressively ---
-    // Payment applies first to this cycle's normal contribution amount.
-    // Any excess beyond `contribution_amount` reduces old `debt`.
-    // extra_applied_to_debt = max(0, new_total - contribution_amount)
-    let extra_applied_to_debt = if new_total > plan.contribution_amount {
-        new_total.checked_sub(plan.contribution_amount).unwrap_or_default()
-    } else {
-        Uint128::zero()
+    let pot = CYCLE_POT.may_load(storage, (plan.id, cycle))?.unwrap_or_default();
+    let (recipient, discount) = match plan.payout_mode {
+        PayoutMode::RoundRobin => {
+            let slot = (cycle % plan.payout_schedule.len() as u64) as usize;
+            (plan.payout_schedule[slot].clone(), Uint128::zero())
+        }
+        PayoutMode::Auction => auction_winner(storage, plan, cycle)?,
     };
-    let new_debt = debt.saturating_sub(extra_applied_to_debt);
+    let recipient = api.addr_validate(&recipient)?;
+    let discount = discount.min(pot);
+    let shared = share_discount(storage, plan, cycle, &recipient, discount)?;
+    // Rounding dust of the discount stays with the recipient
+    let amount = pot - shared;
+    plan.current_cycle += 1;
 
-    // Persist state
-    TRUST_SCORE.save(deps.storage, &sender, &trust_score)?;
-    PLANS.save(deps.storage, plan_id, &plan)?;
-    CONTRIBUTIONS.save(deps.storage, (plan_id, sender.clone(), cycle), &new_total)?;
-    USER_DEBT.save(deps.storage, (plan_id, sender.clone()), &new_debt)?;
+    PAYOUTS.save(
+        storage,
+        (plan.id, cycle),
+        &Payout { recipient: recipient.clone(), amount, discount: shared, paid_at: now },
+    )?;
 
-    // ...right before returning Ok(Response::new()...)
-let mut resp = Response::new()
-    .add_attribute("action", "contribute")
-    .add_attribute("plan_id", plan_id.to_string())
-    .add_attribute("cycle", cycle.to_string())
-    .add_attribute("from", info.sender)
-    .add_attribute("amount", amount.to_string())
-    .add_attribute("contributed_total_this_cycle", new_total.to_string())
-    .add_attribute("debt_after", new_debt.to_string());
+    if plan.current_cycle as u64 >= total_cycles(plan) {
+        transition(plan, PlanStatus::Completed)?;
+    }
 
-// attempt a payout if the pot has enough for one full round
-if let Some(bank_msg) = try_auto_payout(deps, &env, plan_id)? {
-    resp = resp
-        .add_message(bank_msg)
-        .add_attribute("auto_payout", "true");
+    if amount.is_zero() {
+        return Ok(None);
+    }
+    // By default the recipient collects with ClaimPayout, so a contribution
+    // that closes a cycle never pays for (or fails on) someone else's payout
+    if !plan.auto_push {
+        PENDING_PAYOUTS.update::<_, StdError>(storage, (plan.id, recipient), |pending| {
+            Ok(pending.unwrap_or_default() + amount)
+        })?;
+        return Ok(None);
+    }
+    plan.balance = plan
+        .balance
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InvalidInput("underflow".into()))?;
+    Ok(Some(send_funds(plan, &recipient, amount)?))
 }
 
-Ok(resp)
+// Pays a debt repayment to the recipients of the cycles `debtor` shorted,
+// oldest first, as if it had been in those pots when they closed. Debt with
+// no recorded cycle (carried over from before migration) goes into `cycle`'s
+// pot instead.
+fn repay_debt(
+    storage: &mut dyn Storage,
+    plan: &mut Plan,
+    debtor: &Addr,
+    cycle: u64,
+    amount: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut msgs = Vec::new();
+    let mut left = amount;
+    let owed: Vec<(u64, Uint128)> = DEBT_OWED
+        .prefix((plan.id, debtor.clone()))
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    for (shorted, due) in owed {
+        if left.is_zero() {
+            break;
+        }
+        let Some(mut payout) = PAYOUTS.may_load(storage, (plan.id, shorted))? else {
+            continue;
+        };
+        let paid = due.min(left);
+        left -= paid;
+        if paid == due {
+            DEBT_OWED.remove(storage, (plan.id, debtor.clone(), shorted));
+        } else {
+            DEBT_OWED.save(storage, (plan.id, debtor.clone(), shorted), &(due - paid))?;
+        }
+        payout.amount += paid;
+        PAYOUTS.save(storage, (plan.id, shorted), &payout)?;
+
+        if plan.auto_push {
+            plan.balance -= paid;
+            msgs.push(send_funds(plan, &payout.recipient, paid)?);
+        } else {
+            PENDING_PAYOUTS.update::<_, StdError>(storage, (plan.id, payout.recipient), |pending| {
+                Ok(pending.unwrap_or_default() + paid)
+            })?;
+        }
+    }
 
+    if !left.is_zero() {
+        CYCLE_POT.update::<_, StdError>(storage, (plan.id, cycle), |pot| {
+            Ok(pot.unwrap_or_default() + left)
+        })?;
+    }
+    Ok(msgs)
 }
 
+// Members who may still win the auction: everyone who has not been paid in
+// the current rotation round (the fewest payouts so far), in schedule order
+fn auction_eligible(storage: &dyn Storage, plan: &Plan) -> StdResult<Vec<String>> {
+    let received = payouts_received(storage, plan)?;
+    let fewest = received.iter().map(|(_, paid)| *paid).min().unwrap_or_default();
+    Ok(received
+        .into_iter()
+        .filter(|(_, paid)| *paid == fewest)
+        .map(|(member, _)| member)
+        .collect())
+}
 
-fn try_auto_payout(
-    deps: DepsMut,
-    _env: &Env,
-    plan_id: u64,
-) -> Result<Option<BankMsg>, ContractError> {
-    let mut plan = PLANS.load(deps.storage, plan_id)?;
+// Payouts each scheduled member has received so far, in schedule order
+fn payouts_received(storage: &dyn Storage, plan: &Plan) -> StdResult<Vec<(String, u32)>> {
+    let mut received: Vec<(String, u32)> =
+        plan.payout_schedule.iter().map(|member| (member.clone(), 0)).collect();
+    for item in PAYOUTS.prefix(plan.id).range(storage, None, None, Order::Ascending) {
+        let (_, payout) = item?;
+        if let Some((_, paid)) = received.iter_mut().find(|(member, _)| *member == payout.recipient.as_str()) {
+            *paid += 1;
+        }
+    }
+    Ok(received)
+}
 
-    if !plan.is_active {
-        return Ok(None);
+// Removes a member who crossed the missed-cycle limit while `cycle` closed.
+// Their remaining collateral goes into this cycle's pot, their trust score
+// takes the default penalty, and the rotation is re-dealt among the others
+// without the cycles that would still have paid the defaulter.
+fn eject_defaulter(
+    storage: &mut dyn Storage,
+    now: u64,
+    plan: &mut Plan,
+    addr: &Addr,
+    cycle: u64,
+    missed: u32,
+) -> Result<(), ContractError> {
+    let seized = COLLATERAL.may_load(storage, (plan.id, addr.clone()))?.unwrap_or_default();
+    COLLATERAL.remove(storage, (plan.id, addr.clone()));
+    if !seized.is_zero() {
+        CYCLE_POT.update::<_, StdError>(storage, (plan.id, cycle), |pot| {
+            Ok(pot.unwrap_or_default() + seized)
+        })?;
     }
 
-    let total_required =
-        plan.contribution_amount * Uint128::from(plan.participants.len() as u128);
+    let before = trust::score(storage, addr, now)?;
+    let after = trust::apply(storage, addr, now, TrustReason::Defaulted, Some(plan.id), Some(cycle))?;
 
-    // Only trigger when one full round is fundable
-    if plan.balance < total_required {
-        return Ok(None);
+    if let Some(mut votes) = CANCEL_VOTES.may_load(storage, plan.id)? {
+        votes.retain(|voter| voter != addr);
+        CANCEL_VOTES.save(storage, plan.id, &votes)?;
     }
 
-    // Pay exactly one round; keep any extra in balance
-    plan.balance = plan
-        .balance
-        .checked_sub(total_required)
-        .map_err(|_| ContractError::InvalidInput("underflow".into()))?;
+    let len = plan.payout_schedule.len() as u64;
+    let received = payouts_received(storage, plan)?;
+    let rotation: Vec<(String, u32)> = (0..len)
+        .map(|offset| received[((cycle + offset) % len) as usize].clone())
+        .collect();
+    let cycles_left = total_cycles(plan).saturating_sub(cycle);
+    let (schedule, dropped) = schedule::redeal(&rotation, addr.as_str(), cycle, cycles_left);
+    plan.payout_schedule = schedule;
+    plan.total_cycles = plan.total_cycles.saturating_sub(dropped);
+    plan.participants.retain(|member| member != addr.as_str());
+
+    let debt = USER_DEBT.may_load(storage, (plan.id, addr.clone()))?.unwrap_or_default();
+    DEFAULTS.save(
+        storage,
+        (plan.id, addr.clone()),
+        &MemberDefaulted {
+            member: addr.clone(),
+            cycle,
+            missed_cycles: missed,
+            debt,
+            collateral_seized: seized,
+            trust_penalty: before - after,
+        },
+    )?;
+    Ok(())
+}
 
-    // Round-robin recipient
-    let recipient = deps
-        .api
-        .addr_validate(&plan.participants[plan.payout_index as usize])?;
+// Ends a plan whose open `cycle` can no longer be paid out. Members' shares
+// for it become credit again, whatever defaulters left in the pot is split
+// evenly between them, and earlier cycles are settled by `open_refunds`.
+fn wind_down(storage: &mut dyn Storage, plan: &mut Plan, cycle: u64, status: PlanStatus) -> Result<(), ContractError> {
+    let mut pot = CYCLE_POT.may_load(storage, (plan.id, cycle))?.unwrap_or_default();
+    CYCLE_POT.remove(storage, (plan.id, cycle));
+
+    let mut returned = Vec::new();
+    for member in &plan.participants {
+        let addr = Addr::unchecked(member);
+        let key = (plan.id, addr.clone(), cycle);
+        let paid = CONTRIBUTIONS.may_load(storage, key.clone())?.unwrap_or_default();
+        CONTRIBUTIONS.remove(storage, key);
+        pot = pot.saturating_sub(paid);
+        returned.push((addr, paid));
+    }
+    let members = returned.len() as u128;
+    let share = pot.multiply_ratio(1u128, members.max(1));
+    // Rounding dust goes to the first member
+    let mut dust = pot - share * Uint128::from(members);
+    for (addr, paid) in returned {
+        let credit = paid + share + std::mem::take(&mut dust);
+        if credit.is_zero() {
+            continue;
+        }
+        CREDITS.update::<_, StdError>(storage, (plan.id, addr), |held| {
+            Ok(held.unwrap_or_default() + credit)
+        })?;
+    }
+    transition(plan, status)?;
+    open_refunds(storage, plan)
+}
 
-    plan.payout_index = (plan.payout_index + 1) % plan.participants.len() as u32;
-    plan.current_cycle += 1;
+// Highest discount wins; ties and an empty book fall back to schedule order
+fn auction_winner(storage: &dyn Storage, plan: &Plan, cycle: u64) -> StdResult<(String, Uint128)> {
+    let eligible = auction_eligible(storage, plan)?;
+    let mut winner: Option<(String, Uint128)> = None;
+    for member in &eligible {
+        let Some(bid) = BIDS.may_load(storage, (plan.id, cycle, Addr::unchecked(member)))? else {
+            continue;
+        };
+        if winner.as_ref().is_none_or(|(_, best)| bid > *best) {
+            winner = Some((member.clone(), bid));
+        }
+    }
+    Ok(winner.unwrap_or_else(|| {
+        let fallback = eligible.first().unwrap_or(&plan.payout_schedule[0]);
+        (fallback.clone(), Uint128::zero())
+    }))
+}
 
-    // Optional: end plan after enough global cycles
-    let total_cycles = plan.duration_months * cycles_per_month(&plan.frequency);
-	if plan.current_cycle >= total_cycles {
-		plan.is_active = false;
-	}
+// Credits the winner's discount to the other members, pro-rata to what each
+// put into this cycle's pot. Returns the amount actually shared.
+fn share_discount(
+    storage: &mut dyn Storage,
+    plan: &Plan,
+    cycle: u64,
+    winner: &Addr,
+    discount: Uint128,
+) -> StdResult<Uint128> {
+    if discount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    let mut weights = Vec::new();
+    let mut total_weight = Uint128::zero();
+    for member in &plan.participants {
+        let addr = Addr::unchecked(member);
+        if addr == *winner {
+            continue;
+        }
+        let paid = CONTRIBUTIONS
+            .may_load(storage, (plan.id, addr.clone(), cycle))?
+            .unwrap_or_default();
+        total_weight += paid;
+        weights.push((addr, paid));
+    }
+    if total_weight.is_zero() {
+        return Ok(Uint128::zero());
+    }
 
-    PLANS.save(deps.storage, plan_id, &plan)?;
+    let mut shared = Uint128::zero();
+    for (addr, paid) in weights {
+        let share = discount.multiply_ratio(paid, total_weight);
+        if share.is_zero() {
+            continue;
+        }
+        shared += share;
+        CREDITS.update::<_, StdError>(storage, (plan.id, addr.clone()), |credit| {
+            Ok(credit.unwrap_or_default() + share)
+        })?;
+        DIVIDENDS.update::<_, StdError>(storage, (plan.id, addr), |total| {
+            Ok(total.unwrap_or_default() + share)
+        })?;
+    }
+    Ok(shared)
+}
 
-    // NOTE: we are NOT zeroing per-user contributions here because your design uses
-    // per-participant “personal cycles” (based on PARTICIPANT_START). If you later
-    // switch to shared/global cycles, you’ll want to reset per-cycle buckets.
 
-    let bank_msg = BankMsg::Send {
-        to_address: recipient.to_string(),
-        amount: vec![Coin {
-            denom: "uxion".to_string(),
-            amount: total_required,
-        }],
-    };
+// Native denoms must be non-empty; CW20 contracts must answer TokenInfo
+fn check_denom(deps: Deps, denom: UncheckedDenom) -> Result<Denom, ContractError> {
+    if matches!(&denom, UncheckedDenom::Native(name) if name.is_empty()) {
+        return Err(ContractError::InvalidInput("Invalid denom".to_string()));
+    }
+    Ok(denom.into_checked(deps)?)
+}
+
+fn denom_label(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(name) => name.clone(),
+        Denom::Cw20(contract) => contract.to_string(),
+    }
+}
+
+// Amount of the plan's native denom attached to the message; any other coin
+// (or any coin at all for a CW20 plan) is rejected
+fn funds_in(info: &MessageInfo, denom: &Denom) -> Result<Uint128, ContractError> {
+    let mut sent = Uint128::zero();
+    for coin in &info.funds {
+        if *denom != Denom::Native(coin.denom.clone()) {
+            return Err(ContractError::WrongDenom {
+                expected: denom_label(denom),
+                received: coin.denom.clone(),
+            });
+        }
+        sent += coin.amount;
+    }
+    Ok(sent)
+}
 
-    Ok(Some(bank_msg))
+fn send_funds(plan: &Plan, to: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match &plan.denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: to.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }
+        .into(),
+        Denom::Cw20(contract) => WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: to.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
 }
 
+// Records how each badge mint went; see `mint_badges`
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != badge::BADGE_REPLY_ID {
+        return Err(ContractError::InvalidInput(format!("Unknown reply id {}", msg.id)));
+    }
+    let key: (u64, Addr) = from_json(&msg.payload)?;
+    let mut badge = BADGES.load(deps.storage, key.clone())?;
+    badge.status = match msg.result {
+        SubMsgResult::Ok(_) => BadgeStatus::Minted,
+        SubMsgResult::Err(error) => BadgeStatus::Failed { error },
+    };
+    BADGES.save(deps.storage, key.clone(), &badge)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "badge_reply")
+        .add_attribute("plan_id", key.0.to_string())
+        .add_attribute("member", key.1)
+        .add_attribute("minted", (badge.status == BadgeStatus::Minted).to_string()))
+}
 
 #[entry_point]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::GetConfig {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::GetSolvency {} => to_json_binary(&query_solvency(deps, &_env)?),
+        QueryMsg::GetInsurancePool {} => to_json_binary(&query_insurance_pool(deps)?),
         QueryMsg::GetPlan { plan_id } => {
             let plan = query_plan(deps, plan_id)
                 .map_err(|e| StdError::generic_err(e.to_string()))?;
@@ -408,10 +2476,164 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary(&query_participant_cycle_status(deps, _env, plan_id, participant)?)
         }
         QueryMsg::GetTrustScore { user } => { 
-            let res = query_trust_score(deps, user)?;
+            let res = query_trust_score(deps, &_env, user)?;
             to_json_binary(&res)
         }
+        QueryMsg::Reputation { subject } => to_json_binary(&query_reputation(deps, &_env, subject)?),
+        QueryMsg::GetAttesters {} => {
+            let attesters: Vec<AttesterResponse> = ATTESTERS
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(attester, weight_bps)| AttesterResponse { attester, weight_bps }))
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&attesters)
+        }
+        QueryMsg::GetAttestation { attester, evidence_hash } => {
+            let attester = deps.api.addr_validate(&attester)?;
+            to_json_binary(&ATTESTATIONS.may_load(deps.storage, (&attester, &evidence_hash.to_ascii_lowercase()))?)
+        }
+        QueryMsg::GetTrustHistory { user, start_after, limit } => {
+            to_json_binary(&query_trust_history(deps, user, start_after, limit)?)
+        }
+        QueryMsg::GetTrustBreakdown { user } => {
+            to_json_binary(&query_trust_breakdown(deps, &_env, user)?)
+        }
+        QueryMsg::GetJoinEligibility { plan_id, member } => {
+            to_json_binary(&query_join_eligibility(deps, &_env, plan_id, member)?)
+        }
+        QueryMsg::GetPayoutSchedule { plan_id } => {
+            to_json_binary(&query_payout_schedule(deps, plan_id)?)
+        }
+        QueryMsg::GetLotteryDraw { plan_id } => {
+            to_json_binary(&query_lottery_draw(deps, plan_id)?)
+        }
+        QueryMsg::GetAuction { plan_id } => {
+            to_json_binary(&query_auction(deps, plan_id)?)
+        }
+        QueryMsg::GetPendingPayout { plan_id, recipient } => {
+            let addr = deps.api.addr_validate(&recipient)?;
+            let pending = PENDING_PAYOUTS.may_load(deps.storage, (plan_id, addr))?.unwrap_or_default();
+            to_json_binary(&pending)
+        }
+        QueryMsg::GetCollateral { plan_id, member } => {
+            let addr = deps.api.addr_validate(&member)?;
+            let held = COLLATERAL.may_load(deps.storage, (plan_id, addr))?.unwrap_or_default();
+            to_json_binary(&held)
+        }
+        QueryMsg::GetBadges { plan_id } => {
+            let badges: Vec<Badge> = BADGES
+                .prefix(plan_id)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, badge)| badge))
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&badges)
+        }
+        QueryMsg::GetInsuranceClaims { plan_id } => {
+            let claims: Vec<InsuranceClaim> = INSURANCE_CLAIMS
+                .prefix(plan_id)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, claim)| claim))
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&claims)
+        }
+        QueryMsg::GetDefaults { plan_id } => {
+            let defaults: Vec<MemberDefaulted> = DEFAULTS
+                .prefix(plan_id)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, record)| record))
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&defaults)
+        }
+        QueryMsg::GetRefund { plan_id, participant } => {
+            to_json_binary(&query_refund(deps, plan_id, participant)?)
+        }
+    }
+}
+
+// Each reserve against the coverage its running insured plans could still
+// claim (their remaining per-plan allowance)
+fn query_insurance_pool(deps: Deps) -> StdResult<InsurancePoolResponse> {
+    let pool = INSURANCE_POOL.load(deps.storage)?;
+    let mut assets: Vec<PoolAsset> = pool
+        .reserves
+        .iter()
+        .map(|reserve| PoolAsset {
+            denom: reserve.denom.clone(),
+            reserve: reserve.amount,
+            claims_paid: Uint128::zero(),
+            exposure: Uint128::zero(),
+        })
+        .collect();
+
+    for item in PLANS.range(deps.storage, None, None, Order::Ascending) {
+        let (plan_id, plan) = item?;
+        if !plan.insured {
+            continue;
+        }
+        let claimed = insurance_claimed(deps.storage, plan_id)?;
+        let exposure = if plan.status.is_finished() {
+            Uint128::zero()
+        } else {
+            pool.max_cover_per_plan.saturating_sub(claimed)
+        };
+        match assets.iter_mut().find(|asset| asset.denom == plan.denom) {
+            Some(asset) => {
+                asset.claims_paid += claimed;
+                asset.exposure += exposure;
+            }
+            None => assets.push(PoolAsset {
+                denom: plan.denom,
+                reserve: Uint128::zero(),
+                claims_paid: claimed,
+                exposure,
+            }),
+        }
+    }
+
+    let solvent = assets.iter().all(|asset| asset.reserve >= asset.exposure);
+    Ok(InsurancePoolResponse {
+        premium_bps: pool.premium_bps,
+        max_cover_per_cycle: pool.max_cover_per_cycle,
+        max_cover_per_plan: pool.max_cover_per_plan,
+        solvent,
+        assets,
+    })
+}
+
+// Sums `Plan::balance` per asset and compares it with what the contract
+// actually holds; anything held beyond that is untracked
+fn query_solvency(deps: Deps, env: &Env) -> StdResult<SolvencyResponse> {
+    let mut assets: Vec<AssetHoldings> = Vec::new();
+    for item in PLANS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, plan) = item?;
+        match assets.iter_mut().find(|asset| asset.denom == plan.denom) {
+            Some(asset) => asset.tracked += plan.balance,
+            None => assets.push(AssetHoldings { denom: plan.denom, tracked: plan.balance, held: Uint128::zero() }),
+        }
+    }
+    // Insurance reserves are held alongside the plans' funds
+    for reserve in INSURANCE_POOL.load(deps.storage)?.reserves {
+        match assets.iter_mut().find(|asset| asset.denom == reserve.denom) {
+            Some(asset) => asset.tracked += reserve.amount,
+            None => assets.push(AssetHoldings { denom: reserve.denom, tracked: reserve.amount, held: Uint128::zero() }),
+        }
+    }
+
+    let contract = env.contract.address.to_string();
+    for asset in assets.iter_mut() {
+        asset.held = match &asset.denom {
+            Denom::Native(denom) => deps.querier.query_balance(&contract, denom)?.amount,
+            Denom::Cw20(token) => {
+                let res: BalanceResponse = deps.querier.query_wasm_smart(
+                    token,
+                    &Cw20QueryMsg::Balance { address: contract.clone() },
+                )?;
+                res.balance
+            }
+        };
     }
+
+    let solvent = assets.iter().all(|asset| asset.held >= asset.tracked);
+    Ok(SolvencyResponse { solvent, assets })
 }
 
 fn query_plan(deps: Deps, plan_id: u64) -> Result<PlanResponse, ContractError> {
@@ -436,30 +2658,142 @@ fn query_plans_by_creator(
     Ok(plans)
 }
 
+fn query_reputation(deps: Deps, env: &Env, subject: String) -> StdResult<ReputationResponse> {
+    let subject = deps.api.addr_validate(&subject)?;
+    Ok(ReputationResponse {
+        score: trust::score(deps.storage, &subject, env.block.time.seconds())?,
+        baseline: trust::BASELINE,
+        max_score: trust::MAX_SCORE,
+        updated_at: trust::updated_at(deps.storage, &subject)?,
+        subject,
+    })
+}
+
+fn query_trust_history(
+    deps: Deps,
+    user: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TrustHistoryResponse> {
+    let user = deps.api.addr_validate(&user)?;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let events = TRUST_EVENTS
+        .prefix(&user)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, event)| TrustHistoryEntry { id, event }))
+        .collect::<StdResult<_>>()?;
+    Ok(TrustHistoryResponse { events })
+}
+
+// Replays the user's event log into per-plan counts and points. Together
+// with the baseline and decay, the points add up to the current score
+// (scores from before the log existed excepted).
+fn query_trust_breakdown(deps: Deps, env: &Env, user: String) -> StdResult<TrustBreakdownResponse> {
+    let user = deps.api.addr_validate(&user)?;
+    let score = trust::score(deps.storage, &user, env.block.time.seconds())?;
+    let mut decay_points = score as i64 - TRUST_SCORE.may_load(deps.storage, &user)?.unwrap_or(trust::BASELINE) as i64;
+    let mut attestation_points = 0;
+    let mut plans: Vec<PlanTrustBreakdown> = Vec::new();
+
+    for item in TRUST_EVENTS.prefix(&user).range(deps.storage, None, None, Order::Ascending) {
+        let (_, event) = item?;
+        let Some(plan_id) = event.plan_id else {
+            match event.reason {
+                TrustReason::Attestation { .. } => attestation_points += event.delta,
+                _ => decay_points += event.delta,
+            }
+            continue;
+        };
+        let entry = match plans.iter_mut().position(|entry| entry.plan_id == plan_id) {
+            Some(index) => &mut plans[index],
+            None => {
+                plans.push(PlanTrustBreakdown { plan_id, ..Default::default() });
+                plans.last_mut().unwrap()
+            }
+        };
+        match event.reason {
+            TrustReason::OnTimeContribution => {
+                entry.on_time += 1;
+                entry.on_time_points += event.delta;
+            }
+            TrustReason::LateContribution | TrustReason::DebtRepayment => {
+                entry.late += 1;
+                entry.late_points += event.delta;
+            }
+            TrustReason::PartialContribution => {
+                entry.partial += 1;
+                entry.partial_points += event.delta;
+            }
+            TrustReason::MissedCycle => {
+                entry.missed += 1;
+                entry.missed_points += event.delta;
+            }
+            _ => entry.other_points += event.delta,
+        }
+    }
+
+    Ok(TrustBreakdownResponse { score, baseline: trust::BASELINE, decay_points, attestation_points, plans })
+}
+
+fn query_join_eligibility(deps: Deps, env: &Env, plan_id: u64, member: String) -> StdResult<JoinEligibilityResponse> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let member = deps.api.addr_validate(&member)?;
+    let (trust_score, pledged_trust) = effective_trust_score(deps.storage, plan_id, &member, env.block.time.seconds())?;
+    let waiver_votes = TRUST_WAIVERS.may_load(deps.storage, (plan_id, member.clone()))?.unwrap_or_default();
+    let waived = trust_waived(deps.storage, &plan, &member)?;
+    Ok(JoinEligibilityResponse {
+        trust_score,
+        pledged_trust,
+        required: plan.trust_score_required,
+        waiver_votes,
+        waived,
+        eligible: waived || trust_score + pledged_trust >= plan.trust_score_required,
+    })
+}
+
 fn query_trust_score(
 	deps: Deps,
+	env: &Env,
 	user: String
 ) -> StdResult<u64> {
 	let account: Addr = deps.api.addr_validate(&user)?;
-
-	let trust_score = TRUST_SCORE
-		.may_load(deps.storage, &account)?
-		.unwrap_or(50);
-
-	Ok(trust_score)
+	trust::score(deps.storage, &account, env.block.time.seconds())
 }
 
-pub fn request_to_join_plan(
+fn execute_request_to_join_plan(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     plan_id: u64,
 ) -> Result<Response, ContractError> {
-    let requester = info.sender.clone();
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let deposit = funds_in(&info, &plan.denom)?;
+    request_to_join_plan(deps, env, info.sender, plan, deposit)
+}
+
+pub fn request_to_join_plan(
+    deps: DepsMut,
+    env: Env,
+    requester: Addr,
+    mut plan: Plan,
+    deposit: Uint128,
+) -> Result<Response, ContractError> {
+    let plan_id = plan.id;
+
+    ensure_recruiting(&plan)?;
+    if plan.participants.contains(&requester.to_string()) {
+        return Err(ContractError::AlreadyParticipant {});
+    }
+    if plan.participants.len() as u32 >= plan.total_participants {
+        return Err(ContractError::PlanFull {});
+    }
 
     // Check for existing request
     if JOIN_REQUESTS.has(deps.storage, (plan_id, requester.clone())) {
         return Err(ContractError::AlreadyRequested {});
     }
+    ensure_trusted(deps.storage, &plan, &requester, env.block.time.seconds())?;
 
     let new_request = JoinRequest {
         plan_id,
@@ -470,7 +2804,15 @@ pub fn request_to_join_plan(
 
     JOIN_REQUESTS.save(deps.storage, (plan_id, requester.clone()), &new_request)?;
 
-    Ok(Response::new()
+    // Held until the request is decided; returned if it is denied
+    let excess = escrow_collateral(deps.storage, &mut plan, &requester, deposit)?;
+    PLANS.save(deps.storage, plan_id, &plan)?;
+
+    let mut resp = Response::new();
+    if !excess.is_zero() {
+        resp = resp.add_message(send_funds(&plan, &requester, excess)?);
+    }
+    Ok(resp
         .add_attribute("action", "request_to_join_plan")
         .add_attribute("plan_id", plan_id.to_string())
         .add_attribute("requester", requester))
@@ -487,6 +2829,14 @@ pub fn approve_join_request(
     let mut plan = PLANS.load(deps.storage, plan_id)?;
     let key = (plan_id, requester_addr.clone());
 
+    ensure_recruiting(&plan)?;
+    if plan.participants.len() as u32 >= plan.total_participants {
+        return Err(ContractError::PlanFull {});
+    }
+    ensure_join_voter(&plan, &info.sender)?;
+    // Scores can drop (or pledges be withdrawn) after the request was made
+    ensure_trusted(deps.storage, &plan, &requester_addr, _env.block.time.seconds())?;
+
     // First, update approvals inside the closure
     let updated_request = JOIN_REQUESTS.update::<_, ContractError>(deps.storage, key.clone(), |maybe_request| {
         let mut request = maybe_request.ok_or(ContractError::NotFound {})?;
@@ -499,31 +2849,70 @@ pub fn approve_join_request(
         Ok::<JoinRequest, ContractError>(request)
     })?;
 
-	let mut trust_score = TRUST_SCORE
-		.may_load(deps.storage, &requester_addr)?
-		.unwrap_or(50);
-
     // Now apply side effects *after* the update to avoid borrow conflict
-    if updated_request.approvals.len() * 2 >= plan.participants.len() {
-        // 50%+ approved: add to participants, save plan, remove request
-        let insert_at = plan.payout_index as usize;
-		plan.participants.insert(insert_at, requester_addr.to_string());
-		trust_score += 2;
-
-        let now = _env.block.time.seconds();
-        let requester_addr = deps.api.addr_validate(&requester)?;
-        PARTICIPANT_START.save(deps.storage, (plan_id, requester_addr.clone()), &now)?;
-        PLANS.save(deps.storage, plan_id, &plan)?;
+    if tally_join_votes(&plan, &updated_request) == JoinTally::Approved {
+		plan.participants.push(requester_addr.to_string());
         JOIN_REQUESTS.remove(deps.storage, key);
-		TRUST_SCORE.save(deps.storage, &requester_addr, &trust_score)?;
+		if plan.participants.len() as u32 == plan.total_participants {
+			start_plan(deps.storage, &_env, &mut plan, _env.block.time.seconds())?;
+		}
+        PLANS.save(deps.storage, plan_id, &plan)?;
+		let now = _env.block.time.seconds();
+		trust::apply(deps.storage, &requester_addr, now, TrustReason::JoinApproved, Some(plan_id), None)?;
     }
 
     Ok(Response::new()
         .add_attribute("action", "approve_join_request")
         .add_attribute("plan_id", plan_id.to_string())
-        .add_attribute("requester", requester))
+        .add_attribute("requester", requester)
+        .add_attribute("status", plan.status.to_string()))
+}
+
+
+#[derive(PartialEq)]
+enum JoinTally {
+    Pending,
+    Approved,
+    Denied,
+}
+
+// Only participants vote on join requests, and only the creator under
+// `JoinPolicy::CreatorOnly`
+fn ensure_join_voter(plan: &Plan, voter: &Addr) -> Result<(), ContractError> {
+    if !plan.participants.contains(&voter.to_string()) {
+        return Err(ContractError::NotParticipant {});
+    }
+    if plan.join_policy == JoinPolicy::CreatorOnly && *voter != plan.created_by {
+        return Err(ContractError::Unauthorized("Only the plan creator decides join requests".to_string()));
+    }
+    Ok(())
 }
 
+// Decides a join request under the plan's policy. A request is denied once
+// enough voters have refused that the rest can no longer approve it.
+fn tally_join_votes(plan: &Plan, request: &JoinRequest) -> JoinTally {
+    let members = plan.participants.len() as u64;
+    let voters = if plan.join_policy == JoinPolicy::CreatorOnly { 1 } else { members };
+    let required = match &plan.join_policy {
+        JoinPolicy::Open => members.div_ceil(2),
+        JoinPolicy::CreatorOnly => 1,
+        JoinPolicy::Quorum { percent } => (members * *percent as u64).div_ceil(100),
+        JoinPolicy::Threshold { approvals } if members >= *approvals as u64 => *approvals as u64,
+        // Until there are enough participants to reach the threshold, every
+        // one of them (at first just the creator) has to approve
+        JoinPolicy::Threshold { .. } => members,
+        JoinPolicy::Unanimous => members,
+    }
+    .max(1);
+
+    if request.approvals.len() as u64 >= required {
+        JoinTally::Approved
+    } else if voters.saturating_sub(request.denials.len() as u64) < required {
+        JoinTally::Denied
+    } else {
+        JoinTally::Pending
+    }
+}
 
 fn query_join_requests(deps: Deps, plan_id: u64) -> StdResult<JoinRequestsResponse> {
     let requests = JOIN_REQUESTS
@@ -535,8 +2924,13 @@ fn query_join_requests(deps: Deps, plan_id: u64) -> StdResult<JoinRequestsRespon
             }
         })
         .collect();
+    let guarantees = GUARANTEES
+        .sub_prefix(plan_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, pledge)| pledge))
+        .collect::<StdResult<_>>()?;
 
-    Ok(JoinRequestsResponse { requests })
+    Ok(JoinRequestsResponse { requests, guarantees })
 }
 
 pub fn deny_join_request(
@@ -547,9 +2941,12 @@ pub fn deny_join_request(
     requester: String,
 ) -> Result<Response, ContractError> {
     let requester_addr = deps.api.addr_validate(&requester)?;
-    let plan = PLANS.load(deps.storage, plan_id)?;
+    let mut plan = PLANS.load(deps.storage, plan_id)?;
     let key = (plan_id, requester_addr.clone());
 
+    ensure_recruiting(&plan)?;
+    ensure_join_voter(&plan, &info.sender)?;
+
     let updated_request = JOIN_REQUESTS.update::<_, ContractError>(deps.storage, key.clone(), |maybe_request| {
         let mut request = maybe_request.ok_or(ContractError::NotFound {})?;
 
@@ -561,18 +2958,94 @@ pub fn deny_join_request(
         Ok::<JoinRequest, ContractError>(request)
     })?;
 
-    if updated_request.denials.len() * 2 > plan.participants.len() {
-        // More than 50% denied: remove request
-        JOIN_REQUESTS.remove(deps.storage, key);
+    let mut resp = Response::new();
+    if tally_join_votes(&plan, &updated_request) == JoinTally::Denied {
+        // Remove the request and return the collateral
+        JOIN_REQUESTS.remove(deps.storage, key.clone());
+        let collateral = COLLATERAL.may_load(deps.storage, key.clone())?.unwrap_or_default();
+        if !collateral.is_zero() {
+            COLLATERAL.remove(deps.storage, key);
+            plan.balance = plan
+                .balance
+                .checked_sub(collateral)
+                .map_err(|_| ContractError::InvalidInput("underflow".into()))?;
+            PLANS.save(deps.storage, plan_id, &plan)?;
+            resp = resp.add_message(send_funds(&plan, &requester_addr, collateral)?);
+        }
     }
 
-    Ok(Response::new()
+    Ok(resp
         .add_attribute("action", "deny_join_request")
         .add_attribute("plan_id", plan_id.to_string())
         .add_attribute("requester", requester))
 }
 
 
+fn query_payout_schedule(deps: Deps, plan_id: u64) -> StdResult<PayoutScheduleResponse> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let frozen = !matches!(plan.status, PlanStatus::Recruiting | PlanStatus::Drawing);
+    let next_recipient = if plan.status == PlanStatus::Active && !plan.payout_schedule.is_empty() {
+        let slot = plan.current_cycle as usize % plan.payout_schedule.len();
+        Some(plan.payout_schedule[slot].clone())
+    } else {
+        None
+    };
+    Ok(PayoutScheduleResponse {
+        order: plan.payout_order,
+        frozen,
+        schedule: plan.payout_schedule,
+        current_cycle: plan.current_cycle,
+        next_recipient,
+    })
+}
+
+fn query_lottery_draw(deps: Deps, plan_id: u64) -> StdResult<LotteryDrawResponse> {
+    let draw = LOTTERY_DRAWS.may_load(deps.storage, plan_id)?;
+    let committed = DRAW_COMMITS
+        .prefix(plan_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    let revealed = DRAW_REVEALS
+        .prefix(plan_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    Ok(LotteryDrawResponse { draw, committed, revealed })
+}
+
+fn query_auction(deps: Deps, plan_id: u64) -> StdResult<AuctionResponse> {
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let cycle = plan.current_cycle as u64;
+    let pot = CYCLE_POT.may_load(deps.storage, (plan_id, cycle))?.unwrap_or_default();
+    let eligible = if plan.payout_mode == PayoutMode::Auction && plan.status == PlanStatus::Active {
+        auction_eligible(deps.storage, &plan)?
+    } else {
+        vec![]
+    };
+    let bids = BIDS
+        .prefix((plan_id, cycle))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(bidder, discount)| Bid { bidder, discount }))
+        .collect::<StdResult<_>>()?;
+    Ok(AuctionResponse {
+        cycle: plan.current_cycle,
+        pot,
+        eligible,
+        bids,
+    })
+}
+
+fn query_refund(deps: Deps, plan_id: u64, participant: String) -> StdResult<RefundResponse> {
+    let addr = deps.api.addr_validate(&participant)?;
+    let plan = PLANS.load(deps.storage, plan_id)?;
+    let cancel_votes = CANCEL_VOTES.may_load(deps.storage, plan_id)?.unwrap_or_default();
+    let mut claimable = REFUNDS.may_load(deps.storage, (plan_id, addr.clone()))?.unwrap_or_default()
+        + CREDITS.may_load(deps.storage, (plan_id, addr.clone()))?.unwrap_or_default();
+    if addr == plan.created_by {
+        claimable += plan.keeper_reserve;
+    }
+    Ok(RefundResponse { cancel_votes, claimable })
+}
+
 fn query_participant_cycle_status(
     deps: Deps,
     env: Env,
@@ -582,57 +3055,227 @@ fn query_participant_cycle_status(
     let plan = PLANS.load(deps.storage, plan_id)?;
     let addr = deps.api.addr_validate(&participant)?;
 
-    let start = PARTICIPANT_START.may_load(deps.storage, (plan_id, addr.clone()))?;
-    
-    let frequency_str = match &plan.frequency {
-        Frequency::Daily => "Daily",
-        Frequency::Weekly => "Weekly",
-        Frequency::Monthly => "Monthly",
-    };
-    let cycle = current_cycle_for_participant(start.unwrap_or(0), &env, frequency_str);
+    let cycle = plan_cycle(&plan, env.block.time.seconds());
 
-    let contributed = CONTRIBUTIONS
+    let mut contributed = CONTRIBUTIONS
         .may_load(deps.storage, (plan_id, addr.clone(), cycle))?
         .unwrap_or_else(Uint128::zero);
 
-    let debt = USER_DEBT
+    let mut debt = USER_DEBT
         .may_load(deps.storage, (plan_id, addr.clone()))?
         .unwrap_or_else(Uint128::zero);
 
+    let mut credit = CREDITS
+        .may_load(deps.storage, (plan_id, addr.clone()))?
+        .unwrap_or_default();
+
+    // Cycles that have ended but were not closed yet take credit first, then
+    // roll into debt; the running cycle takes what credit is left
+    if plan.status == PlanStatus::Active {
+        for past in plan.current_cycle as u64..cycle {
+            let paid = CONTRIBUTIONS
+                .may_load(deps.storage, (plan_id, addr.clone(), past))?
+                .unwrap_or_default();
+            let shortfall = plan.contribution_amount.saturating_sub(paid);
+            let used = credit.min(shortfall);
+            credit -= used;
+            debt += shortfall - used;
+        }
+        if cycle >= plan.current_cycle as u64 {
+            let used = credit.min(plan.contribution_amount.saturating_sub(contributed));
+            credit -= used;
+            contributed += used;
+        }
+    }
+
     let required = plan.contribution_amount;
     let remaining = required.saturating_sub(contributed);
     let fully = contributed >= required;
 
+    let now = env.block.time.seconds();
+    let deadline = cycle_deadline(&plan, cycle);
+    let late_amount = match deadline {
+        Some(deadline) if now > deadline => remaining + debt,
+        _ => debt,
+    };
+    let late_fee_due = late_fee(deps.storage, &plan, &addr, cycle, late_amount)?;
+    let late_fees_paid = LATE_FEES
+        .prefix((plan_id, addr.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |acc, item| item.map(|(_, fee)| acc + fee))?;
+    let missed_cycles = MISSED_CYCLES.may_load(deps.storage, (plan_id, addr.clone()))?.unwrap_or_default();
+
     Ok(ParticipantCycleStatusResponse {
-        cycle: cycle,
+        cycle,
         required,
         contributed_this_cycle: contributed,
         remaining_this_cycle: remaining,
         fully_contributed: fully,
-        debt: debt,
+        debt,
+        credit,
+        prepaid_cycles: (credit / plan.contribution_amount).u128() as u64,
+        deadline,
+        late_fee_due,
+        late_fees_paid,
+        missed_cycles,
     })
 }
 
-fn cycles_between(start: u64, now: u64, frequency: &str) -> u64 {
-    if now <= start { return 0; }
-    let seconds = now - start;
-    match frequency {
-        "Daily" | "daily" => seconds / 86_400,
-        "Weekly" | "weekly" => seconds / (7 * 86_400),
-        "Monthly" | "monthly" => seconds / (30 * 86_400),
-        _ => 0,
+// The single authoritative cycle index: derived from the shared plan start
+// and the frequency. Cycle 0 until the plan has started.
+fn plan_cycle(plan: &Plan, now: u64) -> u64 {
+    match plan.start_time {
+        Some(start) => calendar::cycles_between(&plan.frequency, start, now),
+        None => 0,
+    }
+}
+
+fn total_cycles(plan: &Plan) -> u64 {
+    plan.total_cycles as u64
+}
+
+// Locks membership and anchors cycle 0 for every member at `start_at`.
+// Lottery plans first run a commit-reveal draw for their payout order and
+// start once it is finalized.
+fn start_plan(
+    storage: &mut dyn Storage,
+    env: &Env,
+    plan: &mut Plan,
+    start_at: u64,
+) -> Result<(), ContractError> {
+    if !plan.collateral_amount.is_zero() {
+        for member in &plan.participants {
+            let held = COLLATERAL
+                .may_load(storage, (plan.id, Addr::unchecked(member)))?
+                .unwrap_or_default();
+            if held < plan.collateral_amount {
+                return Err(ContractError::CollateralMissing { member: member.clone() });
+            }
+        }
+    }
+
+    if plan.payout_order == PayoutOrder::Lottery {
+        let now = env.block.time.seconds();
+        transition(plan, PlanStatus::Drawing)?;
+        LOTTERY_DRAWS.save(
+            storage,
+            plan.id,
+            &LotteryDraw {
+                start_at,
+                commit_deadline: now + DRAW_COMMIT_WINDOW,
+                reveal_deadline: now + DRAW_COMMIT_WINDOW + DRAW_REVEAL_WINDOW,
+            },
+        )?;
+    } else {
+        transition(plan, PlanStatus::Active)?;
+        plan.start_time = Some(start_at);
+        plan.payout_schedule = build_payout_schedule(storage, env.block.time.seconds(), plan)?;
+    }
+
+    // Membership is locked, so outstanding requests can no longer be approved
+    let pending: Vec<Addr> = JOIN_REQUESTS
+        .prefix(plan.id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for requester in pending {
+        JOIN_REQUESTS.remove(storage, (plan.id, requester));
+    }
+    Ok(())
+}
+
+// Freezes the rotation order according to `plan.payout_order`
+fn build_payout_schedule(storage: &dyn Storage, now: u64, plan: &Plan) -> StdResult<Vec<String>> {
+    let schedule = match plan.payout_order {
+        PayoutOrder::JoinOrder => plan.participants.clone(),
+        PayoutOrder::Fixed => {
+            // Members the creator did not place are paid last, in join order
+            let mut order: Vec<String> = plan
+                .payout_schedule
+                .iter()
+                .filter(|member| plan.participants.contains(member))
+                .cloned()
+                .collect();
+            for member in &plan.participants {
+                if !order.contains(member) {
+                    order.push(member.clone());
+                }
+            }
+            order
+        }
+        PayoutOrder::TrustScore => {
+            let mut scored = Vec::with_capacity(plan.participants.len());
+            for member in &plan.participants {
+                let score = trust::score(storage, &Addr::unchecked(member), now)?;
+                scored.push((member.clone(), score));
+            }
+            schedule::rank_by_score(&scored)
+        }
+        PayoutOrder::Lottery => {
+            let mut reveals = Vec::new();
+            for member in &plan.participants {
+                if let Some(secret) = DRAW_REVEALS.may_load(storage, (plan.id, Addr::unchecked(member)))? {
+                    reveals.push((member.clone(), secret));
+                }
+            }
+            schedule::draw_order(plan.id, &plan.participants, &reveals)
+        }
+    };
+    Ok(schedule)
+}
+
+// Freezes the drawn order and starts the rotation
+fn finish_draw(storage: &mut dyn Storage, now: u64, plan: &mut Plan) -> Result<(), ContractError> {
+    let draw = LOTTERY_DRAWS.load(storage, plan.id)?;
+    transition(plan, PlanStatus::Active)?;
+    plan.start_time = Some(draw.start_at.max(now));
+    plan.payout_schedule = build_payout_schedule(storage, now, plan)?;
+    Ok(())
+}
+
+fn load_draw(storage: &dyn Storage, plan: &Plan) -> Result<LotteryDraw, ContractError> {
+    if plan.status != PlanStatus::Drawing {
+        return Err(ContractError::DrawPhaseNotOpen { phase: "draw".to_string() });
+    }
+    Ok(LOTTERY_DRAWS.load(storage, plan.id)?)
+}
+
+fn count_entries(
+    storage: &dyn Storage,
+    map: &Map<(u64, Addr), String>,
+    plan_id: u64,
+) -> StdResult<usize> {
+    Ok(map
+        .prefix(plan_id)
+        .keys(storage, None, None, Order::Ascending)
+        .count())
+}
+
+fn transition(plan: &mut Plan, to: PlanStatus) -> Result<(), ContractError> {
+    if !plan.status.can_transition_to(&to) {
+        return Err(ContractError::InvalidStatusTransition {
+            from: plan.status.to_string(),
+            to: to.to_string(),
+        });
     }
+    plan.status = to;
+    Ok(())
 }
 
-// Per-participant current cycle: uses their personal start time
-fn current_cycle_for_participant(start_time: u64, env: &Env, frequency: &str) -> u64 {
-    cycles_between(start_time, env.block.time.seconds(), frequency)
+// Membership changes are only allowed while the plan is still recruiting
+fn ensure_recruiting(plan: &Plan) -> Result<(), ContractError> {
+    match plan.status {
+        PlanStatus::Recruiting => Ok(()),
+        PlanStatus::Drawing => Err(ContractError::DrawInProgress {}),
+        PlanStatus::Active => Err(ContractError::PlanActive {}),
+        _ => Err(ContractError::PlanFinished { status: plan.status.to_string() }),
+    }
 }
 
-fn cycles_per_month(freq: &Frequency) -> u32 {
-    match freq {
-        Frequency::Daily => 30,   // rough month
-        Frequency::Weekly => 4,   // 4 weeks ≈ month
-        Frequency::Monthly => 1,
+// Contributions and payouts are only allowed once the rotation is running
+fn ensure_active(plan: &Plan) -> Result<(), ContractError> {
+    match plan.status {
+        PlanStatus::Active => Ok(()),
+        PlanStatus::Recruiting | PlanStatus::Drawing => Err(ContractError::PlanNotActive {}),
+        _ => Err(ContractError::PlanFinished { status: plan.status.to_string() }),
     }
 }
@@ -21,6 +21,33 @@ pub enum ContractError {
     #[error("Plan is not active")]
     PlanNotActive {},
 
+    #[error("Plan starts at {start_time}")]
+    PlanNotStarted { start_time: u64 },
+
+    #[error("Plan has already finished ({status})")]
+    PlanFinished { status: String },
+
+    #[error("Plan cannot move from {from} to {to}")]
+    InvalidStatusTransition { from: String, to: String },
+
+    #[error("Plan needs at least {min} participants to start")]
+    NotEnoughParticipants { min: u32 },
+
+    #[error("Lottery draw is in progress")]
+    DrawInProgress {},
+
+    #[error("The {phase} phase of the draw is not open")]
+    DrawPhaseNotOpen { phase: String },
+
+    #[error("You have already committed a secret")]
+    AlreadyCommitted {},
+
+    #[error("You have already revealed your secret")]
+    AlreadyRevealed {},
+
+    #[error("Secret does not match your commitment")]
+    CommitmentMismatch {},
+
     #[error("Plan is already full")]
     PlanFull {},
 
@@ -33,6 +60,12 @@ pub enum ContractError {
     #[error("Your trust score is too low to join this plan")]
     InsufficientTrustScore {},
 
+    #[error("Wrong denomination: expected {expected}, got {received}")]
+    WrongDenom { expected: String, received: String },
+
+    #[error("Denomination {denom} is not allowed")]
+    DenomNotAllowed { denom: String },
+
     #[error("Not enough total contributions to distribute payout")]
     InsufficientContributions {},
 
@@ -45,6 +78,21 @@ pub enum ContractError {
     #[error("Already approved")]
     AlreadyApproved {},
 
+    #[error("You have already voted")]
+    AlreadyVoted {},
+
+    #[error("{member} has not posted the required collateral")]
+    CollateralMissing { member: String },
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Attestation {evidence_hash} was already submitted")]
+    DuplicateAttestation { evidence_hash: String },
+
+    #[error("No cycle is past its deadline yet")]
+    CycleNotDue {},
+
 	#[error("Item not found")]
     NotFound,
 }
@@ -1,11 +1,16 @@
 #[cfg(not(feature = "library"))]
 pub mod contract;
 
+pub mod badge;
+pub mod calendar;
 pub mod error;
 pub mod msg;
+pub mod reputation;
+pub mod schedule;
 pub mod state;
+pub mod trust;
 
 #[cfg(not(feature = "library"))]
 pub use crate::contract::{
-    execute, instantiate, query,
+    execute, instantiate, migrate, query, reply,
 };
@@ -1,32 +1,82 @@
-use crate::state::JoinRequest;
+use crate::state::{Guarantee, JoinRequest};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::{Uint128};
+use cosmwasm_std::{Addr, Uint128};
 use cosmwasm_schema::QueryResponses;
+use cw20::{Cw20ReceiveMsg, Denom, UncheckedDenom};
 
-use crate::state::{Plan};
+use crate::reputation::ReputationResponse;
+use crate::state::{Attestation, Badge, Config, FeeDestination, Frequency, InsuranceClaim, JoinPolicy, LateFee, LotteryDraw, MemberDefaulted, PayoutMode, PayoutOrder, Plan, TrustEvent};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    pub allowed_denoms: Option<Vec<UncheckedDenom>>, // defaults to native "uxion"
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     CreatePlan {
         name: String,
         description: String,
         total_participants: u32,
         contribution_amount: Uint128,
-        frequency: String,
-        duration_months: u32,
-        trust_score_required: u32,
+        denom: Option<UncheckedDenom>,     // defaults to native "uxion"; must be allowlisted
+        frequency: Frequency,
+        total_cycles: u32,
+        trust_score_required: u64,
         allow_partial: bool,
+        payout_order: Option<PayoutOrder>, // defaults to JoinOrder
+        payout_mode: Option<PayoutMode>,   // defaults to RoundRobin
+        auto_push: Option<bool>,           // defaults to false (recipients claim)
+        keeper_tip: Option<Uint128>,       // defaults to zero (no tip)
+        grace_period_seconds: Option<u64>,
+        late_fee: Option<LateFee>,                     // defaults to no fee
+        late_fee_destination: Option<FeeDestination>,  // defaults to Pot
+        collateral_amount: Option<Uint128>,            // defaults to none
+        max_missed_cycles: Option<u32>,                // defaults to never ejecting
+        insured: Option<bool>,                         // defaults to false
+        join_policy: Option<JoinPolicy>,               // defaults to Open
     },
+    // Joining and requesting escrow the plan's collateral from the funds sent
     JoinPlan {
         plan_id: u64,
     },
 	RequestToJoinPlan { 
 		plan_id: u64 
 	},
+	// Creator posts their collateral before the plan starts; members top up
+	// after a seizure
+	DepositCollateral {
+		plan_id: u64,
+	},
+	// Returns collateral once the plan has ended, or to a requester who was
+	// not admitted (withdrawing cancels a pending request)
+	WithdrawCollateral {
+		plan_id: u64,
+	},
+	// Participant votes to let `member` in below the plan's trust
+	// requirement; granted once every participant has voted
+	WaiveTrustRequirement {
+		plan_id: u64,
+		member: String,
+	},
+	// Vouches for `member` with the funds sent and/or `trust_pledge` points
+	// of the sender's trust score
+	Guarantee {
+		plan_id: u64,
+		member: String,
+		trust_pledge: Option<u64>,
+	},
+	// Returns a guarantor's unused funds once the plan has ended, or while
+	// the member is not in the plan (which also drops the guarantee)
+	WithdrawGuarantee {
+		plan_id: u64,
+		member: String,
+	},
     ApproveJoinRequest { 
 		plan_id: u64, 
 		requester: String 
@@ -39,10 +89,150 @@ pub enum ExecuteMsg {
 		plan_id: u64, 
 		amount: Uint128 
 	},
+	// Prepays `cycles` future shares into the member's credit, which is
+	// used up automatically as each cycle opens
+	ContributeAhead {
+		plan_id: u64,
+		cycles: u32,
+	},
+	// CW20 plans: send tokens to this contract with a `ContributeHookMsg`
+	// (token contracts call it as `receive`)
+	#[serde(rename = "receive")]
+	Receive(Cw20ReceiveMsg),
+	// Creator locks membership and fixes the shared start time (defaults to
+	// the current block time, at most one cycle ahead)
+	StartPlan {
+		plan_id: u64,
+		start_at: Option<u64>,
+	},
+	// Creator cancels while recruiting; once active, a majority of
+	// participants must call this to cancel
+	CancelPlan {
+		plan_id: u64,
+	},
+	// Lottery draw: commit hex(sha256(secret)), then reveal `secret` once
+	// every member has committed or the commit window has closed
+	CommitSecret {
+		plan_id: u64,
+		commitment: String,
+	},
+	RevealSecret {
+		plan_id: u64,
+		secret: String,
+	},
+	// Anyone may finish the draw after the reveal deadline
+	FinalizeDraw {
+		plan_id: u64,
+	},
+	// Auction mode: offer to take the current cycle's pot minus `discount`
+	PlaceBid {
+		plan_id: u64,
+		discount: Uint128,
+	},
+	// Auction mode: pay out the current cycle once its pot is fully funded
+	CloseAuction {
+		plan_id: u64,
+	},
+	// Creator assigns the rotation order of a `PayoutOrder::Fixed` plan
+	SetPayoutOrder {
+		plan_id: u64,
+		order: Vec<String>,
+	},
+	ClaimRefund {
+		plan_id: u64,
+	},
+	// Collects payouts recorded for the sender when their cycles closed
+	ClaimPayout {
+		plan_id: u64,
+	},
+	// Anyone may close cycles whose deadline has passed; pays the plan's
+	// keeper tip from its reserve when there is one
+	AdvanceCycle {
+		plan_id: u64,
+	},
+	// Admin adds or removes denoms new plans may use; existing plans keep theirs
+	UpdateAllowedDenoms {
+		add: Vec<UncheckedDenom>,
+		remove: Vec<Denom>,
+	},
+	// Creator tops up the keeper reserve with the funds sent; whatever is
+	// left when the plan ends goes back to them through ClaimRefund
+	FundKeeperReserve {
+		plan_id: u64,
+	},
+	// Admin sets the insurance premium and coverage caps; omitted fields are
+	// left as they are
+	UpdateInsurancePool {
+		premium_bps: Option<u16>,
+		max_cover_per_cycle: Option<Uint128>,
+		max_cover_per_plan: Option<Uint128>,
+	},
+	// Admin registers an attester with a weight in basis points, or removes
+	// it when `weight_bps` is omitted
+	SetAttester {
+		attester: String,
+		weight_bps: Option<u16>,
+	},
+	// Admin sets the CW721 contract completion badges are minted on, or
+	// stops minting them when `contract` is omitted
+	SetBadgeContract {
+		contract: Option<String>,
+	},
+	// Registered attesters import a score change; see `reputation`
+	SubmitAttestation {
+		subject: String,
+		delta: i64,
+		evidence_hash: String,
+	},
+	// Creator toggles whether payouts are sent as soon as a cycle closes
+	SetAutoPush {
+		plan_id: u64,
+		enabled: bool,
+	},
+}
+
+// Embedded in `Cw20ReceiveMsg::msg`. For `Contribute`, `amount` is the
+// contribution itself; the tokens sent must also cover any late fee and
+// insurance premium, and the rest is returned.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ContributeHookMsg {
+	Contribute {
+		plan_id: u64,
+		amount: Uint128,
+	},
+	ContributeAhead {
+		plan_id: u64,
+		cycles: u32,
+	},
+	FundKeeperReserve {
+		plan_id: u64,
+	},
+	JoinPlan {
+		plan_id: u64,
+	},
+	RequestToJoinPlan {
+		plan_id: u64,
+	},
+	DepositCollateral {
+		plan_id: u64,
+	},
+	Guarantee {
+		plan_id: u64,
+		member: String,
+		trust_pledge: Option<u64>,
+	},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 pub enum QueryMsg {
+    #[returns(Config)]
+    GetConfig {},
+    // Tracked plan balances against the contract's actual holdings
+    #[returns(SolvencyResponse)]
+    GetSolvency {},
+    // Insurance reserves against the coverage insured plans may still claim
+    #[returns(InsurancePoolResponse)]
+    GetInsurancePool {},
     #[returns(PlanResponse)]
     GetPlan {
         plan_id: u64,
@@ -66,6 +256,78 @@ pub enum QueryMsg {
 	GetTrustScore {
 		user: String
 	},
+	// Stable score lookup for other contracts; see `reputation`
+	#[returns(ReputationResponse)]
+	Reputation {
+		subject: String,
+	},
+	#[returns(Vec<AttesterResponse>)]
+	GetAttesters {},
+	#[returns(Option<Attestation>)]
+	GetAttestation {
+		attester: String,
+		evidence_hash: String,
+	},
+	// Every change to the user's score, oldest first
+	#[returns(TrustHistoryResponse)]
+	GetTrustHistory {
+		user: String,
+		start_after: Option<u64>, // event id
+		limit: Option<u32>,
+	},
+	// The user's score explained per plan, from the same event log
+	#[returns(TrustBreakdownResponse)]
+	GetTrustBreakdown {
+		user: String,
+	},
+	// Whether `member` meets the plan's trust requirement, counting
+	// guarantors' pledged trust and any waiver vote
+	#[returns(JoinEligibilityResponse)]
+	GetJoinEligibility {
+		plan_id: u64,
+		member: String,
+	},
+	#[returns(PayoutScheduleResponse)]
+	GetPayoutSchedule {
+		plan_id: u64,
+	},
+	#[returns(LotteryDrawResponse)]
+	GetLotteryDraw {
+		plan_id: u64,
+	},
+	#[returns(AuctionResponse)]
+	GetAuction {
+		plan_id: u64,
+	},
+	#[returns(Uint128)]
+	GetPendingPayout {
+		plan_id: u64,
+		recipient: String,
+	},
+	#[returns(Uint128)]
+	GetCollateral {
+		plan_id: u64,
+		member: String,
+	},
+	// Members ejected from the plan for missing too many cycles
+	#[returns(Vec<MemberDefaulted>)]
+	GetDefaults {
+		plan_id: u64,
+	},
+	// Completion badges minted for the plan's members
+	#[returns(Vec<Badge>)]
+	GetBadges {
+		plan_id: u64,
+	},
+	#[returns(Vec<InsuranceClaim>)]
+	GetInsuranceClaims {
+		plan_id: u64,
+	},
+	#[returns(RefundResponse)]
+	GetRefund {
+		plan_id: u64,
+		participant: String,
+	},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -81,10 +343,134 @@ pub struct ParticipantCycleStatusResponse {
     pub remaining_this_cycle: Uint128,
     pub fully_contributed: bool,
 	pub debt: Uint128,
+	pub credit: Uint128, // prepaid funds and auction dividends not yet applied
+	pub prepaid_cycles: u64, // whole future shares `credit` covers
+	pub deadline: Option<u64>, // when this cycle's share becomes late
+	pub late_fee_due: Uint128, // fee on paying the remaining share and debt now
+	pub late_fees_paid: Uint128, // all late fees charged in this plan
+	pub missed_cycles: u32, // closed cycles the member did not fully pay
+}
+
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PayoutScheduleResponse {
+	pub order: PayoutOrder,
+	pub frozen: bool,
+	pub schedule: Vec<String>,
+	pub current_cycle: u32,
+	pub next_recipient: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LotteryDrawResponse {
+	pub draw: Option<LotteryDraw>,
+	pub committed: Vec<Addr>,
+	pub revealed: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bid {
+	pub bidder: Addr,
+	pub discount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionResponse {
+	pub cycle: u32,
+	pub pot: Uint128,
+	pub eligible: Vec<String>,
+	pub bids: Vec<Bid>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RefundResponse {
+	pub cancel_votes: Vec<Addr>,
+	pub claimable: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetHoldings {
+	pub denom: Denom,
+	pub tracked: Uint128, // sum of `Plan::balance` in this asset
+	pub held: Uint128,    // bank or CW20 balance of the contract
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SolvencyResponse {
+	pub solvent: bool, // every asset holds at least what the plans track
+	pub assets: Vec<AssetHoldings>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AttesterResponse {
+	pub attester: Addr,
+	pub weight_bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrustHistoryEntry {
+	pub id: u64,
+	pub event: TrustEvent,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrustHistoryResponse {
+	pub events: Vec<TrustHistoryEntry>,
+}
+
+// Counts and the points they added or took away, within one plan
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PlanTrustBreakdown {
+	pub plan_id: u64,
+	pub on_time: u32,
+	pub late: u32, // shares paid after the deadline and payments towards debt
+	pub partial: u32,
+	pub missed: u32,
+	pub on_time_points: i64,
+	pub late_points: i64,
+	pub partial_points: i64,
+	pub missed_points: i64,
+	pub other_points: i64, // admissions, defaults and guarantees drawn
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrustBreakdownResponse {
+	pub score: u64,
+	pub baseline: u64,
+	pub decay_points: i64, // settled and pending drift toward the baseline
+	pub attestation_points: i64, // imported from registered attesters
+	pub plans: Vec<PlanTrustBreakdown>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JoinEligibilityResponse {
+	pub trust_score: u64,
+	pub pledged_trust: u64, // pledged by the member's guarantors in this plan
+	pub required: u64,
+	pub waiver_votes: Vec<Addr>,
+	pub waived: bool,
+	pub eligible: bool,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct JoinRequestsResponse {
     pub requests: Vec<JoinRequest>,
+    pub guarantees: Vec<Guarantee>, // pledges for the plan's requesters and members
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolAsset {
+	pub denom: Denom,
+	pub reserve: Uint128,     // premiums collected minus claims paid
+	pub claims_paid: Uint128, // covered shortfalls in total
+	pub exposure: Uint128,    // coverage running insured plans can still claim
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InsurancePoolResponse {
+	pub premium_bps: u16,
+	pub max_cover_per_cycle: Uint128,
+	pub max_cover_per_plan: Uint128,
+	pub solvent: bool, // every reserve covers its exposure
+	pub assets: Vec<PoolAsset>,
 }
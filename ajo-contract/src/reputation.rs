@@ -0,0 +1,51 @@
+// Portable reputation.
+//
+// The messages here are the part of this contract other contracts may rely
+// on. They serialize exactly like the matching `QueryMsg` and `ExecuteMsg`
+// variants, so a lending app or another savings group can read scores and
+// submit attestations with this module alone. Their shape does not change.
+//
+// Attesters are registered by the admin with a weight in basis points. An
+// attestation is authenticated by its sender, so an attester contract signs
+// by executing the message itself. Each one moves the subject's score by
+// `delta` scaled by the attester's weight, and its evidence hash can only be
+// used once per attester.
+
+use cosmwasm_std::Addr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::trust::MAX_SCORE;
+
+// Largest delta a single attestation may carry, before weighting
+pub const MAX_ATTESTATION_DELTA: i64 = MAX_SCORE as i64;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ReputationQueryMsg {
+    Reputation {
+        subject: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ReputationExecuteMsg {
+    SubmitAttestation {
+        subject: String,
+        delta: i64,
+        evidence_hash: String, // hex sha256 of the off-contract evidence
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReputationResponse {
+    pub subject: Addr,
+    pub score: u64, // decayed to the time of the query
+    pub baseline: u64,
+    pub max_score: u64,
+    pub updated_at: Option<u64>, // None if the score has never changed
+}
+
+// Points an attestation is worth after the attester's weight
+pub fn weighted_delta(delta: i64, weight_bps: u16) -> i64 {
+    delta * weight_bps as i64 / 10_000
+}
@@ -0,0 +1,111 @@
+// Payout-order helpers: ranking and shuffling of plan members.
+
+use sha2::{Digest, Sha256};
+
+// Hashes `parts` in order into a 32-byte seed
+pub fn seed_from(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+// Deterministic Fisher-Yates shuffle: the same members and seed always give
+// the same order. Member order is normalised first, so the result does not
+// depend on the order the list was stored in.
+pub fn shuffle(members: &[String], seed: &[u8; 32]) -> Vec<String> {
+    let mut order = members.to_vec();
+    order.sort();
+    for i in (1..order.len()).rev() {
+        let j = (draw(seed, i as u64) % (i as u64 + 1)) as usize;
+        order.swap(i, j);
+    }
+    order
+}
+
+// The i-th 64-bit draw derived from `seed`
+fn draw(seed: &[u8; 32], i: u64) -> u64 {
+    let digest = Sha256::new()
+        .chain_update(seed)
+        .chain_update(i.to_be_bytes())
+        .finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+// Members ordered by descending score; ties keep their original order
+pub fn rank_by_score(members: &[(String, u64)]) -> Vec<String> {
+    let mut ranked = members.to_vec();
+    ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    ranked.into_iter().map(|(member, _)| member).collect()
+}
+
+// Hex-encoded sha256 of a lottery secret, as submitted in the commit phase
+pub fn commitment(secret: &str) -> String {
+    Sha256::digest(secret.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+// Lottery order from a commit-reveal round. Members who revealed are shuffled
+// with a seed built from every revealed secret, so no single party (including
+// the block proposer) controls the outcome. Members who never revealed go to
+// the back of the rotation in their original order: withholding a secret to
+// steer the draw can only ever cost the withholder.
+pub fn draw_order(plan_id: u64, members: &[String], reveals: &[(String, String)]) -> Vec<String> {
+    let mut reveals: Vec<&(String, String)> = reveals
+        .iter()
+        .filter(|(member, _)| members.contains(member))
+        .collect();
+    reveals.sort();
+
+    let plan_id = plan_id.to_be_bytes();
+    let mut parts: Vec<&[u8]> = vec![&plan_id];
+    for (member, secret) in &reveals {
+        parts.push(member.as_bytes());
+        parts.push(secret.as_bytes());
+    }
+    let seed = seed_from(&parts);
+
+    let revealed: Vec<String> = reveals.iter().map(|(member, _)| member.clone()).collect();
+    let mut order = shuffle(&revealed, &seed);
+    order.extend(members.iter().filter(|member| !revealed.contains(member)).cloned());
+    order
+}
+
+// Re-deals the rotation after `leaving` is ejected. `rotation` lists every
+// member with the payouts they have received so far, starting with the one
+// due at `cycle`. Members paid the fewest times go next (ties keep their
+// rotation order); the returned schedule lines up with `cycle % len`.
+// Also returns how many of the `cycles_left` cycles would still have paid
+// `leaving`, which the plan no longer needs to run.
+pub fn redeal(rotation: &[(String, u32)], leaving: &str, cycle: u64, cycles_left: u64) -> (Vec<String>, u32) {
+    let mut counts = rotation.to_vec();
+    let mut dropped = 0;
+    for _ in 0..cycles_left {
+        let Some(next) = counts.iter_mut().min_by_key(|(_, paid)| *paid) else {
+            break;
+        };
+        next.1 += 1;
+        if next.0 == leaving {
+            dropped += 1;
+        }
+    }
+
+    let mut remaining: Vec<(String, u32)> = rotation
+        .iter()
+        .filter(|(member, _)| member != leaving)
+        .cloned()
+        .collect();
+    remaining.sort_by_key(|(_, paid)| *paid);
+    let len = remaining.len() as u64;
+    let mut schedule = vec![String::new(); remaining.len()];
+    for (i, (member, _)) in remaining.into_iter().enumerate() {
+        schedule[((cycle + i as u64) % len) as usize] = member;
+    }
+    (schedule, dropped)
+}
@@ -1,22 +1,175 @@
 use cosmwasm_std::{Addr, Uint128};
+use cw20::Denom;
+
+use crate::badge::BadgeMetadata;
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub admin: Addr,
+    pub allowed_denoms: Vec<Denom>, // native denoms and CW20 tokens plans may use
+    #[serde(default)]
+    pub badge_contract: Option<Addr>, // CW721 contract minting completion badges
+}
+
+// Config layout before denom allowlisting, read by `migrate`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub admin: Addr,
 }
 
+// Cycle length; month-based variants follow the UTC calendar (see `calendar`)
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum Frequency {
     Daily,
     Weekly,
+    BiWeekly,
     Monthly,
+    Quarterly,
+    Custom { seconds: u64 },
+}
+
+// How the rotation order is decided; frozen into `Plan::payout_schedule`
+// when the plan starts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PayoutOrder {
+    JoinOrder,
+    Fixed,      // creator-assigned with SetPayoutOrder
+    TrustScore, // highest trust score first
+    Lottery,
+}
+
+// Who receives each cycle's pot: the next member in `payout_schedule`, or
+// the member who bids the biggest discount for an early payout
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PayoutMode {
+    RoundRobin,
+    Auction,
+}
+
+// How members get into a recruiting plan. Only participants vote on join
+// requests; shares and thresholds count the participants at the time of the
+// vote, and a `Threshold` the plan cannot reach yet needs every participant.
+// `Open` plans also let anyone in directly with JoinPlan.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum JoinPolicy {
+    Open, // requests need half of the participants, as before policies existed
+    CreatorOnly,
+    Quorum { percent: u8 },
+    Threshold { approvals: u32 }, // below `total_participants`
+    Unanimous,
+}
+
+// Charged on payments made after a cycle's deadline
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum LateFee {
+    Flat { amount: Uint128 }, // once per member per cycle paid late
+    Bps { bps: u16 },         // basis points of the late amount
+}
+
+// Where late fees go
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum FeeDestination {
+    Pot,       // added to the running cycle's payout
+    Recipient, // sent to the member next in line for a payout
+    Treasury { address: Addr },
+}
+
+// Lifecycle of a plan:
+//   Recruiting -> Active -> Completed
+//   Recruiting -> Drawing -> Active (lottery plans draw their order first)
+//   Recruiting | Drawing | Active -> Cancelled
+//   Active -> Dissolved (too few members left to keep rotating)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PlanStatus {
+    Recruiting,
+    Drawing,
+    Active,
+    Completed,
+    Cancelled,
+    Dissolved,
+}
+
+impl PlanStatus {
+    pub fn can_transition_to(&self, next: &PlanStatus) -> bool {
+        matches!(
+            (self, next),
+            (PlanStatus::Recruiting, PlanStatus::Active)
+                | (PlanStatus::Recruiting, PlanStatus::Drawing)
+                | (PlanStatus::Recruiting, PlanStatus::Cancelled)
+                | (PlanStatus::Drawing, PlanStatus::Active)
+                | (PlanStatus::Drawing, PlanStatus::Cancelled)
+                | (PlanStatus::Active, PlanStatus::Completed)
+                | (PlanStatus::Active, PlanStatus::Cancelled)
+                | (PlanStatus::Active, PlanStatus::Dissolved)
+        )
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            PlanStatus::Completed | PlanStatus::Cancelled | PlanStatus::Dissolved
+        )
+    }
+}
+
+impl fmt::Display for PlanStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            PlanStatus::Recruiting => "recruiting",
+            PlanStatus::Drawing => "drawing",
+            PlanStatus::Active => "active",
+            PlanStatus::Completed => "completed",
+            PlanStatus::Cancelled => "cancelled",
+            PlanStatus::Dissolved => "dissolved",
+        };
+        f.write_str(s)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Plan {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    pub total_participants: u32,
+    pub contribution_amount: Uint128,
+    pub denom: Denom, // asset of contributions, payouts and refunds
+    pub frequency: Frequency,
+    pub total_cycles: u32,
+    pub trust_score_required: u64, // same scale as TRUST_SCORE
+    pub allow_partial: bool,
+    pub participants: Vec<String>, // Store as String, convert with addr_validate()
+    pub current_cycle: u32, // oldest cycle not yet closed and paid out
+    pub status: PlanStatus,
+    pub start_time: Option<u64>, // shared cycle-0 anchor, set when the plan starts
+    pub payout_order: PayoutOrder,
+    pub payout_mode: PayoutMode,
+    pub auto_push: bool, // send payouts right away instead of waiting for ClaimPayout
+    pub keeper_tip: Uint128,     // paid to whoever advances an overdue cycle
+    pub keeper_reserve: Uint128, // funds set aside for keeper tips (part of `balance`)
+    // A cycle's share is due `grace_period_seconds` after the cycle opens;
+    // without one, only repaying debt from closed cycles counts as late
+    pub grace_period_seconds: Option<u64>,
+    pub late_fee: Option<LateFee>,
+    pub late_fee_destination: FeeDestination,
+    pub collateral_amount: Uint128, // escrowed by every member; zero for none
+    pub max_missed_cycles: Option<u32>, // members who miss this many shares are ejected
+    pub insured: bool, // pays the pool premium and has shortfalls covered by it
+    pub join_policy: JoinPolicy,
+    // Recipient of cycle `c` is `payout_schedule[c % len]`. While recruiting
+    // with `PayoutOrder::Fixed` this holds the creator's proposed order.
+    pub payout_schedule: Vec<String>,
+	pub balance: Uint128,
+	pub created_by: Addr,
+}
+
+// Plan layout before the lifecycle/global-cycle rework, read by `migrate`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyPlan {
     pub id: u64,
     pub name: String,
     pub description: String,
@@ -26,12 +179,125 @@ pub struct Plan {
     pub duration_months: u32,
     pub trust_score_required: u32,
     pub allow_partial: bool,
-    pub participants: Vec<String>, // Store as String, convert with addr_validate()
+    pub participants: Vec<String>,
     pub current_cycle: u32,
     pub is_active: bool,
     pub payout_index: u32,
-	pub balance: Uint128,
-	pub created_by: Addr,
+    pub balance: Uint128,
+    pub created_by: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Payout {
+    pub recipient: Addr,
+    pub amount: Uint128, // includes debt repaid into the cycle after it closed
+    pub discount: Uint128, // auction discount shared with the other members
+    pub paid_at: u64,
+}
+
+// Contract-wide insurance reserve that insured plans pay premiums into.
+// Reserves are held by this contract but are not part of any plan's balance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct InsurancePool {
+    pub premium_bps: u16, // charged on top of every contribution to an insured plan
+    pub max_cover_per_cycle: Uint128, // most one plan can claim for a single cycle
+    pub max_cover_per_plan: Uint128,  // most one plan can claim over its lifetime
+    pub reserves: Vec<PoolReserve>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolReserve {
+    pub denom: Denom,
+    pub amount: Uint128,
+}
+
+// Shortfalls the pool covered when a plan's cycle closed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InsuranceClaim {
+    pub cycle: u64,
+    pub amount: Uint128,
+    pub members: Vec<Addr>, // members whose missed shares were covered
+}
+
+// Recorded when a member crosses the plan's missed-cycle limit and is ejected
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MemberDefaulted {
+    pub member: Addr,
+    pub cycle: u64, // cycle whose close triggered the default
+    pub missed_cycles: u32,
+    pub debt: Uint128, // unpaid shares left behind
+    pub collateral_seized: Uint128, // remaining collateral moved into the pot
+    pub trust_penalty: u64,
+}
+
+// Commit-reveal round that decides a lottery plan's payout order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LotteryDraw {
+    pub start_at: u64,
+    pub commit_deadline: u64,
+    pub reveal_deadline: u64,
+}
+
+// Why a member's trust score moved (see `trust::delta` for the points)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum TrustReason {
+    OnTimeContribution,
+    LateContribution, // full share paid after the cycle's deadline
+    PartialContribution,
+    DebtRepayment,
+    JoinApproved,
+    MissedCycle,
+    Defaulted,
+    GuaranteeDrawn { pledged: u64 },
+    Decay,
+    Attestation { attester: Addr, evidence_hash: String, points: i64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum BadgeStatus {
+    Pending, // mint sent, reply not seen yet
+    Minted,
+    Failed { error: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Badge {
+    pub member: Addr,
+    pub token_id: String,
+    pub metadata: BadgeMetadata,
+    pub status: BadgeStatus,
+}
+
+// Score change imported from another contract or app
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Attestation {
+    pub attester: Addr,
+    pub subject: Addr,
+    pub delta: i64, // as submitted
+    pub weight_bps: u16, // attester's weight when it was applied
+    pub points: i64, // weighted delta, before clamping
+    pub at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrustEvent {
+    pub reason: TrustReason,
+    pub plan_id: Option<u64>,
+    pub cycle: Option<u64>,
+    pub delta: i64, // change actually applied, after clamping
+    pub score: u64, // score after the change
+    pub at: u64,
+}
+
+// A guarantor's pledge for a member: `funds` cover the member's missed
+// shares, and `trust` is taken off the guarantor's score the first time the
+// member misses a share their own collateral cannot cover
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Guarantee {
+    pub guarantor: Addr,
+    pub member: Addr,
+    pub funds: Uint128, // held in the plan's balance until drawn or withdrawn
+    pub trust: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -49,13 +315,72 @@ pub const CONFIG: Item<Config> = Item::new("config");
 pub const PLAN_COUNT: Item<u64> = Item::new("plan_count");
 pub const PLANS: Map<u64, Plan> = Map::new("plans");
 pub const PLANS_BY_CREATOR: Map<&Addr, Vec<u64>> = Map::new("plans_by_creator");
+pub const INSURANCE_POOL: Item<InsurancePool> = Item::new("insurance_pool");
 
 
-// (plan_id, participant_addr) => amount contributed
+// All cycle keys below are the plan-wide cycle index (see `Plan::start_time`)
+// (plan_id, participant_addr, cycle) => amount contributed
 pub const CONTRIBUTIONS: Map<(u64, Addr, u64), Uint128> = Map::new("contrib");
+// (plan_id, participant_addr) => unpaid shares rolled over from closed cycles
 pub const USER_DEBT: Map<(u64, Addr), Uint128> = Map::new("user_debt");
+// (plan_id, participant_addr, cycle) => part of USER_DEBT left unpaid when
+// `cycle` closed; repayments go to that cycle's recipient
+pub const DEBT_OWED: Map<(u64, Addr, u64), Uint128> = Map::new("debt_owed");
+// (plan_id, cycle) => funds collected for that cycle's payout
+pub const CYCLE_POT: Map<(u64, u64), Uint128> = Map::new("cycle_pot");
+// (plan_id, cycle) => payout made when the cycle closed
+pub const PAYOUTS: Map<(u64, u64), Payout> = Map::new("payouts");
+pub const LOTTERY_DRAWS: Map<u64, LotteryDraw> = Map::new("lottery_draws");
+// (plan_id, participant_addr) => hex sha256 of the participant's secret
+pub const DRAW_COMMITS: Map<(u64, Addr), String> = Map::new("draw_commits");
+// (plan_id, participant_addr) => revealed secret
+pub const DRAW_REVEALS: Map<(u64, Addr), String> = Map::new("draw_reveals");
+// (plan_id, recipient) => payouts recorded but not yet collected with ClaimPayout
+pub const PENDING_PAYOUTS: Map<(u64, Addr), Uint128> = Map::new("pending_payouts");
+// (plan_id, cycle, bidder) => discount offered for that cycle's pot
+pub const BIDS: Map<(u64, u64, Addr), Uint128> = Map::new("bids");
+// (plan_id, participant_addr, cycle) => late fees charged on payments made in that cycle
+pub const LATE_FEES: Map<(u64, Addr, u64), Uint128> = Map::new("late_fees");
+// (plan_id, member_or_requester) => collateral escrowed, seized to cover missed shares
+pub const COLLATERAL: Map<(u64, Addr), Uint128> = Map::new("collateral");
+// (plan_id, participant_addr, cycle) => set once the member's share was paid
+// in full by the cycle's deadline, or out of credit
+pub const ON_TIME_SHARES: Map<(u64, Addr, u64), bool> = Map::new("on_time_shares");
+// (plan_id, participant_addr) => cycles closed without the member's full share
+pub const MISSED_CYCLES: Map<(u64, Addr), u32> = Map::new("missed_cycles");
+// (plan_id, cycle) => shortfall covered by the insurance pool
+pub const INSURANCE_CLAIMS: Map<(u64, u64), InsuranceClaim> = Map::new("insurance_claims");
+// (plan_id, member) => ejection record for members who defaulted
+pub const DEFAULTS: Map<(u64, Addr), MemberDefaulted> = Map::new("defaults");
+// (plan_id, member, guarantor) => pledge vouching for the member
+pub const GUARANTEES: Map<(u64, Addr, Addr), Guarantee> = Map::new("guarantees");
+// guarantor => trust pledged across all their guarantees and not yet drawn
+pub const PLEDGED_TRUST: Map<&Addr, u64> = Map::new("pledged_trust");
+// (plan_id, member) => participants who voted to waive the plan's trust
+// requirement for the member; takes effect once every participant has
+pub const TRUST_WAIVERS: Map<(u64, Addr), Vec<Addr>> = Map::new("trust_waivers");
+// (plan_id, participant_addr) => credit applied to the member's next shares
+pub const CREDITS: Map<(u64, Addr), Uint128> = Map::new("credits");
+// (plan_id, participant_addr) => auction discounts received in total
+pub const DIVIDENDS: Map<(u64, Addr), Uint128> = Map::new("dividends");
+// (plan_id, member) => completion badge minted for the member
+pub const BADGES: Map<(u64, Addr), Badge> = Map::new("badges");
+// plan_id => participants who voted to cancel an active plan
+pub const CANCEL_VOTES: Map<u64, Vec<Addr>> = Map::new("cancel_votes");
+// (plan_id, participant_addr) => refund still to be claimed after cancellation
+pub const REFUNDS: Map<(u64, Addr), Uint128> = Map::new("refunds");
 pub const TRUST_SCORE: Map<&Addr, u64> = Map::new("trust_scores");
+// attester => weight in basis points given to its attestations
+pub const ATTESTERS: Map<&Addr, u16> = Map::new("attesters");
+// (attester, evidence_hash) => attestation already applied
+pub const ATTESTATIONS: Map<(&Addr, &str), Attestation> = Map::new("attestations");
+// user => time decay has been settled up to; moves in whole `DECAY_PERIOD`s
+pub const TRUST_UPDATED: Map<&Addr, u64> = Map::new("trust_updated");
+// (user, sequence) => every change to the user's score, oldest first
+pub const TRUST_EVENTS: Map<(&Addr, u64), TrustEvent> = Map::new("trust_events");
 
 
+// Legacy per-member start times, only read by `migrate`; cycles now count
+// from `Plan::start_time`
 pub const PARTICIPANT_START: Map<(u64, Addr), u64> = Map::new("participant_start");
 
@@ -0,0 +1,145 @@
+// Trust scoring policy.
+//
+// Scores live on a 0-100 scale starting at `BASELINE`. Every change goes
+// through `apply`, which first settles any decay, then clamps the new score
+// to the scale and appends a `TrustEvent` to the member's log, so a score can
+// always be rebuilt from its history. A score drifts back toward the
+// baseline by one point per `DECAY_PERIOD`; activity does not restart that
+// clock, since settling decay only moves it on by the whole periods elapsed.
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+
+use crate::state::{TrustEvent, TrustReason, TRUST_EVENTS, TRUST_SCORE, TRUST_UPDATED};
+
+pub const BASELINE: u64 = 50;
+pub const MAX_SCORE: u64 = 100;
+pub const DECAY_PERIOD: u64 = 30 * 86_400;
+
+// Points a reason moves the score by, before clamping
+pub fn delta(reason: &TrustReason) -> i64 {
+    match reason {
+        TrustReason::OnTimeContribution => 10,
+        TrustReason::LateContribution => 4,
+        TrustReason::PartialContribution => 5,
+        TrustReason::DebtRepayment => 4,
+        TrustReason::JoinApproved => 2,
+        TrustReason::MissedCycle => -15,
+        TrustReason::Defaulted => -30,
+        TrustReason::GuaranteeDrawn { pledged } => -(*pledged as i64),
+        TrustReason::Decay => 0,
+        TrustReason::Attestation { points, .. } => *points,
+    }
+}
+
+// Current score, including decay accrued since it last changed
+pub fn score(storage: &dyn Storage, user: &Addr, now: u64) -> StdResult<u64> {
+    Ok(decayed(storage, user, now)?.0)
+}
+
+// Time of the last logged change to `user`'s score, if any
+pub fn updated_at(storage: &dyn Storage, user: &Addr) -> StdResult<Option<u64>> {
+    let last = TRUST_EVENTS
+        .prefix(user)
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+    Ok(last.map(|(_, event)| event.at))
+}
+
+// Applies `reason` to `user`'s score and logs it. Returns the new score.
+pub fn apply(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    now: u64,
+    reason: TrustReason,
+    plan_id: Option<u64>,
+    cycle: Option<u64>,
+) -> StdResult<u64> {
+    let (current, stored, settled_at) = decayed(storage, user, now)?;
+    if current != stored {
+        log(storage, user, TrustEvent {
+            reason: TrustReason::Decay,
+            plan_id: None,
+            cycle: None,
+            delta: current as i64 - stored as i64,
+            score: current,
+            at: now,
+        })?;
+    }
+
+    let score = (current as i64 + delta(&reason)).clamp(0, MAX_SCORE as i64) as u64;
+    TRUST_SCORE.save(storage, user, &score)?;
+    TRUST_UPDATED.save(storage, user, &settled_at)?;
+    log(storage, user, TrustEvent {
+        reason,
+        plan_id,
+        cycle,
+        delta: score as i64 - current as i64,
+        score,
+        at: now,
+    })?;
+    Ok(score)
+}
+
+// (current, stored) scores and the time decay is settled up to; scores
+// written before the scale was enforced are clamped, and scores that never
+// changed do not decay
+fn decayed(storage: &dyn Storage, user: &Addr, now: u64) -> StdResult<(u64, u64, u64)> {
+    let stored = TRUST_SCORE.may_load(storage, user)?.unwrap_or(BASELINE).min(MAX_SCORE);
+    let Some(updated) = TRUST_UPDATED.may_load(storage, user)? else {
+        return Ok((stored, stored, now));
+    };
+    let steps = now.saturating_sub(updated) / DECAY_PERIOD;
+    let current = if stored > BASELINE {
+        stored.saturating_sub(steps).max(BASELINE)
+    } else {
+        (stored + steps).min(BASELINE)
+    };
+    Ok((current, stored, updated + steps * DECAY_PERIOD))
+}
+
+fn log(storage: &mut dyn Storage, user: &Addr, event: TrustEvent) -> StdResult<()> {
+    let next = TRUST_EVENTS
+        .prefix(user)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    TRUST_EVENTS.save(storage, (user, next), &event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn events_mid_period_do_not_restart_decay() {
+        let mut storage = MockStorage::new();
+        let (steady, active) = (Addr::unchecked("steady"), Addr::unchecked("active"));
+        for user in [&steady, &active] {
+            apply(&mut storage, user, 0, TrustReason::OnTimeContribution, None, None).unwrap();
+        }
+
+        // Half way through the second period one point has decayed
+        let mid = DECAY_PERIOD + DECAY_PERIOD / 2;
+        assert_eq!(apply(&mut storage, &active, mid, TrustReason::JoinApproved, None, None).unwrap(), 61);
+        assert_eq!(TRUST_UPDATED.load(&storage, &active).unwrap(), DECAY_PERIOD);
+
+        // The half period already elapsed still counts towards the next point
+        let end = 2 * DECAY_PERIOD;
+        assert_eq!(score(&storage, &steady, end).unwrap(), 58);
+        assert_eq!(score(&storage, &active, end).unwrap(), 60);
+        assert_eq!(score(&storage, &active, end - 1).unwrap(), 61);
+    }
+
+    #[test]
+    fn scores_below_the_baseline_recover_one_point_per_period() {
+        let mut storage = MockStorage::new();
+        let user = Addr::unchecked("user");
+        apply(&mut storage, &user, 0, TrustReason::MissedCycle, None, None).unwrap();
+        assert_eq!(score(&storage, &user, 3 * DECAY_PERIOD - 1).unwrap(), 37);
+        assert_eq!(score(&storage, &user, 20 * DECAY_PERIOD).unwrap(), BASELINE);
+        assert_eq!(updated_at(&storage, &user).unwrap(), Some(0));
+    }
+}
@@ -0,0 +1,107 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{AuctionResponse, ExecuteMsg, ParticipantCycleStatusResponse, QueryMsg};
+use ajo_contract::state::{PayoutMode, PlanStatus};
+use cosmwasm_std::Uint128;
+use cw_multi_test::AppResponse;
+
+mod common;
+use common::{NewPlan, Suite, WEEK};
+
+// Three members auctioning a 300 pot each week; payouts are pushed
+fn auction_plan() -> Suite {
+    let plan = NewPlan { payout_mode: Some(PayoutMode::Auction), auto_push: Some(true), ..NewPlan::new(3) };
+    let mut suite = Suite::with_plan(&["alice", "bob", "carol"], plan);
+    for who in 1..3 {
+        suite.join(who);
+    }
+    suite
+}
+
+impl Suite {
+    fn bid(&mut self, who: usize, discount: u128) -> Result<AppResponse, ContractError> {
+        self.execute(who, ExecuteMsg::PlaceBid { plan_id: 1, discount: Uint128::new(discount) })
+    }
+
+    fn close(&mut self) -> Result<AppResponse, ContractError> {
+        self.execute(0, ExecuteMsg::CloseAuction { plan_id: 1 })
+    }
+
+    fn auction(&self) -> AuctionResponse {
+        self.app.wrap().query_wasm_smart(&self.ajo, &QueryMsg::GetAuction { plan_id: 1 }).unwrap()
+    }
+
+    fn credit(&self, who: usize) -> u128 {
+        let status: ParticipantCycleStatusResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.ajo,
+                &QueryMsg::GetParticipantCycleStatus { plan_id: 1, participant: self.users[who].to_string() },
+            )
+            .unwrap();
+        status.credit.u128()
+    }
+
+    fn balances(&self) -> [u128; 3] {
+        [0, 1, 2].map(|who| self.balance(who))
+    }
+}
+
+#[test]
+fn highest_discount_wins_and_is_shared_with_the_others() {
+    let mut suite = auction_plan();
+
+    // The discount has to leave something of the 300 pot
+    assert!(matches!(suite.bid(0, 300).unwrap_err(), ContractError::InvalidInput(_)));
+    suite.bid(1, 30).unwrap();
+    suite.bid(2, 60).unwrap();
+    assert_eq!(suite.auction().bids.len(), 2);
+
+    for who in 0..2 {
+        suite.contribute(who, 100).unwrap();
+    }
+    assert!(matches!(suite.close().unwrap_err(), ContractError::InsufficientContributions {}));
+    suite.contribute(2, 100).unwrap();
+    suite.close().unwrap();
+
+    // Carol takes 240; alice and bob paid in equally, so each gets 30 of credit
+    assert_eq!(suite.balances(), [900, 900, 1_140]);
+    assert_eq!((suite.credit(0), suite.credit(1), suite.credit(2)), (30, 30, 0));
+}
+
+#[test]
+fn winners_sit_out_and_empty_books_follow_the_schedule() {
+    let mut suite = auction_plan();
+    suite.bid(2, 60).unwrap();
+    for who in 0..3 {
+        suite.contribute(who, 100).unwrap();
+    }
+    suite.close().unwrap();
+
+    // Carol already won this round
+    suite.advance(WEEK);
+    assert!(matches!(suite.bid(2, 10).unwrap_err(), ContractError::InvalidInput(_)));
+    let auction = suite.auction();
+    assert_eq!(auction.cycle, 1);
+    assert_eq!(auction.eligible, vec![suite.users[0].to_string(), suite.users[1].to_string()]);
+
+    // The dividends pay 30 of alice's and bob's next share; with no bids the
+    // first eligible member in the schedule takes the whole pot
+    suite.contribute(0, 70).unwrap();
+    suite.contribute(1, 70).unwrap();
+    suite.contribute(2, 100).unwrap();
+    assert_eq!(suite.auction().pot, Uint128::new(300));
+    suite.close().unwrap();
+    assert_eq!(suite.balances(), [1_130, 830, 1_040]);
+
+    suite.advance(WEEK);
+    assert_eq!(suite.auction().eligible, vec![suite.users[1].to_string()]);
+    for who in 0..3 {
+        suite.contribute(who, 100).unwrap();
+    }
+    suite.close().unwrap();
+    assert_eq!(suite.balances(), [1_030, 1_030, 940]);
+
+    let plan = suite.plan();
+    assert_eq!((plan.status, plan.balance), (PlanStatus::Completed, Uint128::zero()));
+}
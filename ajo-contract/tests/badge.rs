@@ -0,0 +1,127 @@
+use ajo_contract::badge::{BadgeExecuteMsg, BadgeMetadata};
+use ajo_contract::msg::{ExecuteMsg, QueryMsg};
+use ajo_contract::state::{Badge, BadgeStatus, PlanStatus};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+mod common;
+use common::{NewPlan, Suite, DAY, WEEK};
+
+const OWNERS: Map<&str, Addr> = Map::new("owners");
+
+#[derive(Serialize, Deserialize)]
+enum MockQuery {
+    OwnerOf { token_id: String },
+}
+
+// Stands in for a soulbound CW721: records who owns each token
+fn mock_badges() -> Box<dyn Contract<Empty>> {
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    fn execute(deps: DepsMut, _: Env, _: MessageInfo, msg: BadgeExecuteMsg) -> StdResult<Response> {
+        let BadgeExecuteMsg::Mint { token_id, owner, .. } = msg;
+        let owner = deps.api.addr_validate(&owner)?;
+        OWNERS.save(deps.storage, &token_id, &owner)?;
+        Ok(Response::new())
+    }
+    fn query(deps: Deps, _: Env, msg: MockQuery) -> StdResult<Binary> {
+        let MockQuery::OwnerOf { token_id } = msg;
+        to_json_binary(&OWNERS.load(deps.storage, &token_id)?)
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn paused_badges() -> Box<dyn Contract<Empty>> {
+    fn execute(_: DepsMut, _: Env, _: MessageInfo, _: BadgeExecuteMsg) -> StdResult<Response> {
+        Err(StdError::generic_err("minting paused"))
+    }
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        to_json_binary(&Empty {})
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+// Two members, weekly shares of 100 due two days into each of `cycles`
+// cycles, with badges minted on the given contract. Returns the suite and
+// the badge contract.
+fn setup(badge_contract: Box<dyn Contract<Empty>>, cycles: u32) -> (Suite, Addr) {
+    let mut suite = Suite::new(&["alice", "bob"]);
+    let badge_code = suite.app.store_code(badge_contract);
+    let badges = suite
+        .app
+        .instantiate_contract(badge_code, suite.users[0].clone(), &Empty {}, &[], "badges", None)
+        .unwrap();
+    suite.execute(0, ExecuteMsg::SetBadgeContract { contract: Some(badges.to_string()) }).unwrap();
+    let plan = NewPlan {
+        total_cycles: cycles,
+        auto_push: Some(true),
+        grace_period_seconds: Some(2 * DAY),
+        ..NewPlan::new(2)
+    };
+    suite.execute(0, plan.msg()).unwrap();
+    suite.join(1);
+    (suite, badges)
+}
+
+impl Suite {
+    fn badges(&self) -> Vec<Badge> {
+        self.app.wrap().query_wasm_smart(&self.ajo, &QueryMsg::GetBadges { plan_id: 1 }).unwrap()
+    }
+}
+
+#[test]
+fn completion_mints_badges_with_on_time_ratio() {
+    let (mut suite, badge_contract) = setup(mock_badges(), 3);
+
+    // Alice prepays her last two shares; bob pays cycle 1 after its deadline
+    suite.contribute(0, 100).unwrap();
+    suite.contribute(1, 100).unwrap();
+    suite.execute_with(0, ExecuteMsg::ContributeAhead { plan_id: 1, cycles: 2 }, 200).unwrap();
+    suite.advance(WEEK + 3 * DAY);
+    suite.contribute(1, 100).unwrap();
+    suite.advance(WEEK - 3 * DAY);
+    assert!(suite.badges().is_empty());
+    suite.contribute(1, 100).unwrap();
+    assert_eq!(suite.plan().status, PlanStatus::Completed);
+
+    let badges = suite.badges();
+    assert_eq!(badges.len(), 2);
+    for badge in &badges {
+        assert_eq!(badge.status, BadgeStatus::Minted);
+        let owner: Addr = suite
+            .app
+            .wrap()
+            .query_wasm_smart(&badge_contract, &MockQuery::OwnerOf { token_id: badge.token_id.clone() })
+            .unwrap();
+        assert_eq!(owner, badge.member);
+    }
+    let metadata = |who: usize| badges.iter().find(|badge| badge.member == suite.users[who]).unwrap().metadata.clone();
+    assert_eq!(metadata(0), BadgeMetadata { plan_id: 1, cycles_completed: 3, on_time_bps: 10_000 });
+    assert_eq!(metadata(1), BadgeMetadata { plan_id: 1, cycles_completed: 3, on_time_bps: 6_666 });
+}
+
+#[test]
+fn failed_mint_does_not_block_completion() {
+    let (mut suite, _) = setup(paused_badges(), 2);
+
+    suite.contribute(0, 100).unwrap();
+    suite.contribute(1, 100).unwrap();
+    suite.advance(WEEK);
+    suite.contribute(0, 100).unwrap();
+
+    // Bob misses his last share: no badge for him
+    suite.advance(WEEK);
+    suite.execute(0, ExecuteMsg::AdvanceCycle { plan_id: 1 }).unwrap();
+    assert_eq!(suite.plan().status, PlanStatus::Completed);
+
+    let badges = suite.badges();
+    assert_eq!(badges.len(), 1);
+    assert_eq!(badges[0].member, suite.users[0]);
+    assert!(matches!(&badges[0].status, BadgeStatus::Failed { error } if error.contains("minting paused")));
+}
@@ -0,0 +1,178 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, ParticipantCycleStatusResponse, QueryMsg};
+use ajo_contract::state::{MemberDefaulted, PlanStatus};
+use cosmwasm_std::Uint128;
+
+mod common;
+use common::{NewPlan, Suite, WEEK};
+
+// Creates a weekly plan with 100 per share and 100 collateral; the creator
+// (users[0]) has not posted collateral yet
+fn collateral_plan(names: &[&str], max_missed_cycles: Option<u32>) -> Suite {
+    let plan = NewPlan {
+        auto_push: Some(true),
+        collateral_amount: Some(Uint128::new(100)),
+        max_missed_cycles,
+        ..NewPlan::new(names.len() as u32)
+    };
+    Suite::with_plan(names, plan)
+}
+
+impl Suite {
+    fn collateral(&self, who: usize) -> Uint128 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.ajo,
+                &QueryMsg::GetCollateral { plan_id: 1, member: self.users[who].to_string() },
+            )
+            .unwrap()
+    }
+
+    fn status(&self, who: usize) -> ParticipantCycleStatusResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.ajo,
+                &QueryMsg::GetParticipantCycleStatus { plan_id: 1, participant: self.users[who].to_string() },
+            )
+            .unwrap()
+    }
+
+    fn plan_status(&self) -> PlanStatus {
+        self.plan().status
+    }
+
+    // Creator posts collateral and everyone else joins, which starts the plan
+    fn fill(&mut self) {
+        self.execute_with(0, ExecuteMsg::DepositCollateral { plan_id: 1 }, 100).unwrap();
+        for who in 1..self.users.len() {
+            self.execute_with(who, ExecuteMsg::JoinPlan { plan_id: 1 }, 100).unwrap();
+        }
+        assert_eq!(self.plan_status(), PlanStatus::Active);
+    }
+}
+
+#[test]
+fn collateral_covers_default_after_early_payout() {
+    let mut suite = collateral_plan(&["alice", "bob", "carol"], None);
+    suite.fill();
+
+    // Cycle 0: everyone pays and alice, first in line, takes the pot
+    for who in 0..3 {
+        suite.contribute(who, 100).unwrap();
+    }
+    assert_eq!(suite.balance(0), 1_000 - 100 - 100 + 300);
+
+    // Cycle 1: alice stops paying after her payout
+    suite.advance(WEEK);
+    suite.contribute(1, 100).unwrap();
+    suite.contribute(2, 100).unwrap();
+    suite.advance(WEEK);
+    suite.execute(1, ExecuteMsg::AdvanceCycle { plan_id: 1 }).unwrap();
+
+    // Her collateral made bob's pot whole instead of becoming debt
+    assert_eq!(suite.collateral(0), Uint128::zero());
+    assert_eq!(suite.status(0).debt, Uint128::zero());
+    assert_eq!(suite.balance(1), 1_000 - 100 - 100 - 100 + 300);
+
+    // Cycle 2: nothing left to seize, so the missed share is debt
+    suite.contribute(1, 100).unwrap();
+    suite.contribute(2, 100).unwrap();
+    suite.advance(WEEK);
+    suite.execute(2, ExecuteMsg::AdvanceCycle { plan_id: 1 }).unwrap();
+    assert_eq!(suite.plan_status(), PlanStatus::Completed);
+    assert_eq!(suite.status(0).debt, Uint128::new(100));
+    assert_eq!(suite.balance(2), 1_000 - 100 - 100 - 100 - 100 + 200);
+
+    // Members who kept paying get their full collateral back
+    for who in 1..3 {
+        suite.execute(who, ExecuteMsg::WithdrawCollateral { plan_id: 1 }).unwrap();
+    }
+    assert_eq!(suite.balance(1), 1_000 - 100 - 100 - 100 + 300);
+    assert_eq!(suite.balance(2), 1_000 - 100 - 100 - 100 + 200);
+    let err = suite.execute(0, ExecuteMsg::WithdrawCollateral { plan_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::NothingToClaim {}));
+}
+
+#[test]
+fn joining_requires_collateral() {
+    let mut suite = collateral_plan(&["alice", "bob"], None);
+
+    let err = suite.execute_with(1, ExecuteMsg::JoinPlan { plan_id: 1 }, 50).unwrap_err();
+    assert!(matches!(err, ContractError::CollateralMissing { .. }));
+
+    // The plan cannot start while the creator's collateral is missing
+    let err = suite.execute_with(1, ExecuteMsg::JoinPlan { plan_id: 1 }, 100).unwrap_err();
+    assert!(matches!(err, ContractError::CollateralMissing { .. }));
+
+    suite.execute_with(0, ExecuteMsg::DepositCollateral { plan_id: 1 }, 150).unwrap();
+    assert_eq!(suite.balance(0), 900);
+    suite.execute_with(1, ExecuteMsg::JoinPlan { plan_id: 1 }, 100).unwrap();
+    assert_eq!(suite.plan_status(), PlanStatus::Active);
+
+    // Locked while the plan runs
+    let err = suite.execute(1, ExecuteMsg::WithdrawCollateral { plan_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput(_)));
+}
+
+#[test]
+fn denied_request_returns_collateral() {
+    let mut suite = collateral_plan(&["alice", "bob", "mallory"], None);
+    suite.execute_with(0, ExecuteMsg::DepositCollateral { plan_id: 1 }, 100).unwrap();
+
+    suite.execute_with(2, ExecuteMsg::RequestToJoinPlan { plan_id: 1 }, 100).unwrap();
+    assert_eq!(suite.balance(2), 900);
+    assert_eq!(suite.collateral(2), Uint128::new(100));
+
+    let mallory = suite.users[2].to_string();
+    suite.execute(0, ExecuteMsg::DenyJoinRequest { plan_id: 1, requester: mallory }).unwrap();
+    assert_eq!(suite.balance(2), 1_000);
+    assert_eq!(suite.collateral(2), Uint128::zero());
+}
+
+#[test]
+fn chronic_defaulter_is_ejected() {
+    let mut suite = collateral_plan(&["alice", "bob", "carol"], Some(1));
+    suite.fill();
+
+    for who in 0..3 {
+        suite.contribute(who, 100).unwrap();
+    }
+
+    // Cycle 1: carol skips her share and crosses the limit
+    suite.advance(WEEK);
+    suite.contribute(0, 100).unwrap();
+    suite.contribute(1, 100).unwrap();
+    suite.advance(WEEK);
+    suite.execute(0, ExecuteMsg::AdvanceCycle { plan_id: 1 }).unwrap();
+
+    let carol = suite.users[2].clone();
+    let defaults: Vec<MemberDefaulted> =
+        suite.app.wrap().query_wasm_smart(&suite.ajo, &QueryMsg::GetDefaults { plan_id: 1 }).unwrap();
+    assert_eq!(defaults.len(), 1);
+    assert_eq!(defaults[0].member, carol);
+    assert_eq!(defaults[0].missed_cycles, 1);
+    let score: u64 = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.ajo, &QueryMsg::GetTrustScore { user: carol.to_string() })
+        .unwrap();
+    assert_eq!(score, 50 + 10 - 15 - 30);
+
+    // Her collateral made bob's pot whole, and the cycle that would have paid
+    // her is dropped, so the plan completes with bob's payout
+    let plan = suite.plan();
+    assert!(!plan.participants.contains(&carol.to_string()));
+    assert!(!plan.payout_schedule.contains(&carol.to_string()));
+    assert_eq!(plan.total_cycles, 2);
+    assert_eq!(plan.status, PlanStatus::Completed);
+    assert_eq!(suite.balance(1), 1_000 - 100 - 100 - 100 + 300);
+    assert_eq!(suite.collateral(2), Uint128::zero());
+
+    for who in 0..2 {
+        suite.execute(who, ExecuteMsg::WithdrawCollateral { plan_id: 1 }).unwrap();
+        assert_eq!(suite.balance(who), 1_100);
+    }
+    assert_eq!(suite.balance(2), 800);
+}
@@ -0,0 +1,181 @@
+// Shared setup for the integration suites. Each suite only uses part of it.
+#![allow(dead_code)]
+
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, InstantiateMsg, PlanResponse, QueryMsg};
+use ajo_contract::state::{FeeDestination, Frequency, JoinPolicy, LateFee, PayoutMode, PayoutOrder, Plan};
+use cosmwasm_std::{coins, Addr, Empty, Uint128};
+use cw20::UncheckedDenom;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+pub const DAY: u64 = 86_400;
+pub const WEEK: u64 = 7 * DAY;
+
+pub fn ajo_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            ajo_contract::contract::execute,
+            ajo_contract::contract::instantiate,
+            ajo_contract::contract::query,
+        )
+        .with_reply(ajo_contract::contract::reply)
+        .with_migrate(ajo_contract::contract::migrate),
+    )
+}
+
+// Stores and instantiates the contract with `admin` as its admin and the
+// default denom allowlist
+pub fn instantiate(app: &mut App, admin: &Addr) -> Addr {
+    let code_id = app.store_code(ajo_contract());
+    app.instantiate_contract(code_id, admin.clone(), &InstantiateMsg { allowed_denoms: None }, &[], "ajo", None)
+        .unwrap()
+}
+
+// The contract, instantiated by `users[0]`, in an app where every user holds
+// 1_000uxion. Users are addressed by index; suites add their own helpers in
+// further `impl Suite` blocks.
+pub struct Suite {
+    pub app: App,
+    pub ajo: Addr,
+    pub users: Vec<Addr>,
+}
+
+impl Suite {
+    pub fn new(names: &[&str]) -> Self {
+        let mut app = App::default();
+        let users: Vec<Addr> = names.iter().map(|name| app.api().addr_make(name)).collect();
+        app.init_modules(|router, _, storage| {
+            for user in &users {
+                router.bank.init_balance(storage, user, coins(1_000, "uxion")).unwrap();
+            }
+        });
+        let ajo = instantiate(&mut app, &users[0]);
+        Suite { app, ajo, users }
+    }
+
+    // Also has `users[0]` create `plan`, which becomes plan 1
+    pub fn with_plan(names: &[&str], plan: NewPlan) -> Self {
+        let mut suite = Self::new(names);
+        suite.execute(0, plan.msg()).unwrap();
+        suite
+    }
+
+    pub fn execute(&mut self, who: usize, msg: ExecuteMsg) -> Result<AppResponse, ContractError> {
+        self.execute_with(who, msg, 0)
+    }
+
+    // Sends `funds` uxion along with `msg`
+    pub fn execute_with(&mut self, who: usize, msg: ExecuteMsg, funds: u128) -> Result<AppResponse, ContractError> {
+        let funds = if funds == 0 { vec![] } else { coins(funds, "uxion") };
+        self.app
+            .execute_contract(self.users[who].clone(), self.ajo.clone(), &msg, &funds)
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn join(&mut self, who: usize) {
+        self.execute(who, ExecuteMsg::JoinPlan { plan_id: 1 }).unwrap();
+    }
+
+    // Contributes `amount` to plan 1, sending exactly that
+    pub fn contribute(&mut self, who: usize, amount: u128) -> Result<AppResponse, ContractError> {
+        self.execute_with(who, ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(amount) }, amount)
+    }
+
+    pub fn advance(&mut self, seconds: u64) {
+        self.app.update_block(|block| block.time = block.time.plus_seconds(seconds));
+    }
+
+    pub fn now(&self) -> u64 {
+        self.app.block_info().time.seconds()
+    }
+
+    pub fn balance(&self, who: usize) -> u128 {
+        self.app.wrap().query_balance(&self.users[who], "uxion").unwrap().amount.u128()
+    }
+
+    pub fn plan(&self) -> Plan {
+        let res: PlanResponse = self.app.wrap().query_wasm_smart(&self.ajo, &QueryMsg::GetPlan { plan_id: 1 }).unwrap();
+        res.plan.unwrap()
+    }
+}
+
+// Fields of `ExecuteMsg::CreatePlan`. Suites start from `NewPlan::new` and
+// override what they test:
+//
+//     NewPlan { auto_push: Some(true), ..NewPlan::new(3) }.msg()
+pub struct NewPlan {
+    pub name: String,
+    pub description: String,
+    pub total_participants: u32,
+    pub contribution_amount: Uint128,
+    pub denom: Option<UncheckedDenom>,
+    pub frequency: Frequency,
+    pub total_cycles: u32,
+    pub trust_score_required: u64,
+    pub allow_partial: bool,
+    pub payout_order: Option<PayoutOrder>,
+    pub payout_mode: Option<PayoutMode>,
+    pub auto_push: Option<bool>,
+    pub keeper_tip: Option<Uint128>,
+    pub grace_period_seconds: Option<u64>,
+    pub late_fee: Option<LateFee>,
+    pub late_fee_destination: Option<FeeDestination>,
+    pub collateral_amount: Option<Uint128>,
+    pub max_missed_cycles: Option<u32>,
+    pub insured: Option<bool>,
+    pub join_policy: Option<JoinPolicy>,
+}
+
+impl NewPlan {
+    // Weekly plan for `members` members, 100uxion per share and one cycle
+    // per member
+    pub fn new(members: u32) -> Self {
+        NewPlan {
+            name: "Test circle".to_string(),
+            description: "Weekly savings circle".to_string(),
+            total_participants: members,
+            contribution_amount: Uint128::new(100),
+            denom: None,
+            frequency: Frequency::Weekly,
+            total_cycles: members,
+            trust_score_required: 0,
+            allow_partial: false,
+            payout_order: None,
+            payout_mode: None,
+            auto_push: None,
+            keeper_tip: None,
+            grace_period_seconds: None,
+            late_fee: None,
+            late_fee_destination: None,
+            collateral_amount: None,
+            max_missed_cycles: None,
+            insured: None,
+            join_policy: None,
+        }
+    }
+
+    pub fn msg(self) -> ExecuteMsg {
+        ExecuteMsg::CreatePlan {
+            name: self.name,
+            description: self.description,
+            total_participants: self.total_participants,
+            contribution_amount: self.contribution_amount,
+            denom: self.denom,
+            frequency: self.frequency,
+            total_cycles: self.total_cycles,
+            trust_score_required: self.trust_score_required,
+            allow_partial: self.allow_partial,
+            payout_order: self.payout_order,
+            payout_mode: self.payout_mode,
+            auto_push: self.auto_push,
+            keeper_tip: self.keeper_tip,
+            grace_period_seconds: self.grace_period_seconds,
+            late_fee: self.late_fee,
+            late_fee_destination: self.late_fee_destination,
+            collateral_amount: self.collateral_amount,
+            max_missed_cycles: self.max_missed_cycles,
+            insured: self.insured,
+            join_policy: self.join_policy,
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ContributeHookMsg, ExecuteMsg};
+use ajo_contract::state::{FeeDestination, LateFee, PlanStatus};
+use cosmwasm_std::{to_json_binary, Addr, Empty, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Denom, UncheckedDenom};
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
+
+mod common;
+use common::{NewPlan, Suite, DAY};
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ))
+}
+
+// Alice and bob each hold 1_000 of a CW20 that the contract accepts besides
+// uxion. Returns the suite and the token.
+fn setup() -> (Suite, Addr) {
+    let mut suite = Suite::new(&["alice", "bob"]);
+    let token = new_token(&mut suite, &[0, 1]);
+    let allow = ExecuteMsg::UpdateAllowedDenoms { add: vec![UncheckedDenom::Cw20(token.to_string())], remove: vec![] };
+    suite.execute(0, allow).unwrap();
+    (suite, token)
+}
+
+// A token minted to the given users, 1_000 each
+fn new_token(suite: &mut Suite, holders: &[usize]) -> Addr {
+    let code_id = suite.app.store_code(cw20_contract());
+    suite
+        .app
+        .instantiate_contract(
+            code_id,
+            suite.users[holders[0]].clone(),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Stable".to_string(),
+                symbol: "STBL".to_string(),
+                decimals: 6,
+                initial_balances: holders
+                    .iter()
+                    .map(|who| Cw20Coin { address: suite.users[*who].to_string(), amount: Uint128::new(1_000) })
+                    .collect(),
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "stable",
+            None,
+        )
+        .unwrap()
+}
+
+impl Suite {
+    // Two-member plan paid in `token`, which alice creates and bob joins
+    fn token_plan(&mut self, token: &Addr, plan: NewPlan) {
+        let plan = NewPlan { denom: Some(UncheckedDenom::Cw20(token.to_string())), ..plan };
+        self.execute(0, plan.msg()).unwrap();
+        self.join(1);
+    }
+
+    // Errors from the nested `receive` call only survive as text
+    fn send(&mut self, who: usize, token: &Addr, amount: u128, hook: &ContributeHookMsg) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.users[who].clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.ajo.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(hook)?,
+            },
+            &[],
+        )
+    }
+
+    fn token_balance(&self, token: &Addr, holder: &Addr) -> Uint128 {
+        let res: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(token, &Cw20QueryMsg::Balance { address: holder.to_string() })
+            .unwrap();
+        res.balance
+    }
+}
+
+const SHARE: ContributeHookMsg = ContributeHookMsg::Contribute { plan_id: 1, amount: Uint128::new(100) };
+
+#[test]
+fn cw20_contributions_pay_out_as_transfers() {
+    let (mut suite, token) = setup();
+    suite.token_plan(&token, NewPlan::new(2));
+    assert_eq!(suite.plan().denom, Denom::Cw20(token.clone()));
+
+    for who in 0..2 {
+        suite.send(who, &token, 100, &SHARE).unwrap();
+    }
+    let (alice, bob, ajo) = (suite.users[0].clone(), suite.users[1].clone(), suite.ajo.clone());
+    assert_eq!(suite.token_balance(&token, &ajo), Uint128::new(200));
+
+    // Alice is first in join order and collects the pot
+    suite.execute(0, ExecuteMsg::ClaimPayout { plan_id: 1 }).unwrap();
+    assert_eq!(suite.token_balance(&token, &alice), Uint128::new(1_100));
+    assert_eq!(suite.token_balance(&token, &bob), Uint128::new(900));
+    assert_eq!(suite.token_balance(&token, &ajo), Uint128::zero());
+}
+
+#[test]
+fn cw20_plan_rejects_other_assets() {
+    let (mut suite, token) = setup();
+    suite.token_plan(&token, NewPlan::new(2));
+
+    // Native coins sent to a CW20 plan
+    let err = suite.contribute(0, 100).unwrap_err();
+    assert!(matches!(err, ContractError::WrongDenom { .. }));
+
+    // Tokens from a different CW20 contract
+    let other = new_token(&mut suite, &[0]);
+    let err = suite.send(0, &other, 100, &SHARE).unwrap_err();
+    assert!(err.root_cause().to_string().starts_with("Wrong denomination"));
+    let ajo = suite.ajo.clone();
+    assert_eq!(suite.token_balance(&token, &ajo), Uint128::zero());
+}
+
+#[test]
+fn cw20_plan_requires_allowlisted_token() {
+    let (mut suite, _) = setup();
+    let other = new_token(&mut suite, &[0]);
+
+    let plan = NewPlan { denom: Some(UncheckedDenom::Cw20(other.to_string())), ..NewPlan::new(2) };
+    let err = suite.execute(0, plan.msg()).unwrap_err();
+    assert!(matches!(err, ContractError::DenomNotAllowed { .. }));
+}
+
+#[test]
+fn cw20_refunds_after_cancel() {
+    let (mut suite, token) = setup();
+    suite.token_plan(&token, NewPlan::new(2));
+    suite.send(1, &token, 100, &SHARE).unwrap();
+
+    for who in 0..2 {
+        suite.execute(who, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap();
+    }
+    assert_eq!(suite.plan().status, PlanStatus::Cancelled);
+
+    suite.execute(1, ExecuteMsg::ClaimRefund { plan_id: 1 }).unwrap();
+    let (bob, ajo) = (suite.users[1].clone(), suite.ajo.clone());
+    assert_eq!(suite.token_balance(&token, &bob), Uint128::new(1_000));
+    assert_eq!(suite.token_balance(&token, &ajo), Uint128::zero());
+}
+
+#[test]
+fn cw20_late_fees_come_out_of_the_tokens_sent() {
+    let (mut suite, token) = setup();
+    let plan = NewPlan {
+        grace_period_seconds: Some(2 * DAY),
+        late_fee: Some(LateFee::Flat { amount: Uint128::new(10) }),
+        late_fee_destination: Some(FeeDestination::Pot),
+        ..NewPlan::new(2)
+    };
+    suite.token_plan(&token, plan);
+    suite.advance(3 * DAY);
+
+    // The share alone no longer covers a late payment
+    let err = suite.send(1, &token, 100, &SHARE).unwrap_err();
+    assert!(err.root_cause().to_string().contains("late fee 10"));
+
+    // The fee is taken from the tokens sent and anything beyond it returned
+    suite.send(1, &token, 110, &SHARE).unwrap();
+    suite.send(0, &token, 150, &SHARE).unwrap();
+    let (alice, bob, ajo) = (suite.users[0].clone(), suite.users[1].clone(), suite.ajo.clone());
+    assert_eq!(suite.token_balance(&token, &alice), Uint128::new(890));
+    assert_eq!(suite.token_balance(&token, &ajo), Uint128::new(220));
+
+    suite.execute(0, ExecuteMsg::ClaimPayout { plan_id: 1 }).unwrap();
+    assert_eq!(suite.token_balance(&token, &alice), Uint128::new(1_110));
+    assert_eq!(suite.token_balance(&token, &bob), Uint128::new(890));
+}
@@ -0,0 +1,153 @@
+use ajo_contract::msg::{ExecuteMsg, MigrateMsg, PlanResponse, QueryMsg, SolvencyResponse};
+use ajo_contract::state::{
+    Config, Frequency, LegacyConfig, LegacyPlan, PlanStatus, CONTRIBUTIONS, CYCLE_POT, PARTICIPANT_START, PLAN_COUNT,
+};
+use cosmwasm_std::{coins, from_json, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128};
+use cw20::Denom;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::{Item, Map};
+
+mod common;
+use common::{ajo_contract, instantiate, NewPlan, DAY, WEEK};
+
+fn pending(app: &App, ajo: &Addr, recipient: &Addr) -> u128 {
+    let amount: Uint128 = app
+        .wrap()
+        .query_wasm_smart(ajo, &QueryMsg::GetPendingPayout { plan_id: 1, recipient: recipient.to_string() })
+        .unwrap();
+    amount.u128()
+}
+
+#[test]
+fn debt_repayment_goes_to_the_recipient_of_the_shorted_cycle() {
+    let mut app = App::default();
+    let members = ["alice", "bob", "carol"].map(|name| app.api().addr_make(name));
+    let [alice, bob, carol] = members.clone();
+    app.init_modules(|router, _, storage| {
+        for member in &members {
+            router.bank.init_balance(storage, member, coins(1_000, "uxion")).unwrap();
+        }
+    });
+    let ajo = instantiate(&mut app, &alice);
+    app.execute_contract(alice.clone(), ajo.clone(), &NewPlan::new(3).msg(), &[]).unwrap();
+    for member in [&bob, &carol] {
+        app.execute_contract(member.clone(), ajo.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[]).unwrap();
+    }
+    let contribute = |app: &mut App, who: &Addr, amount: u128| {
+        app.execute_contract(
+            who.clone(),
+            ajo.clone(),
+            &ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(amount) },
+            &coins(amount, "uxion"),
+        )
+        .unwrap();
+    };
+
+    // Carol misses cycle 0, so alice's payout is 100 short
+    contribute(&mut app, &alice, 100);
+    contribute(&mut app, &bob, 100);
+    app.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    contribute(&mut app, &alice, 100);
+    assert_eq!(pending(&app, &ajo, &alice), 200);
+
+    // Her repayment in cycle 1 makes alice whole instead of topping up bob's pot
+    contribute(&mut app, &bob, 100);
+    contribute(&mut app, &carol, 200);
+    assert_eq!(pending(&app, &ajo, &alice), 300);
+    assert_eq!(pending(&app, &ajo, &bob), 300);
+
+    let solvency: SolvencyResponse = app.wrap().query_wasm_smart(&ajo, &QueryMsg::GetSolvency {}).unwrap();
+    assert!(solvency.solvent);
+    let plan: PlanResponse = app.wrap().query_wasm_smart(&ajo, &QueryMsg::GetPlan { plan_id: 1 }).unwrap();
+    assert_eq!(plan.plan.unwrap().balance, Uint128::new(600));
+}
+
+// Writes a plan in the layout used before the shared cycle clock: one plan
+// whose members joined on different days and paid into their own buckets
+fn legacy_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(deps: DepsMut, env: Env, info: MessageInfo, members: [Addr; 2]) -> StdResult<Response> {
+        let now = env.block.time.seconds();
+        let [alice, bob] = members;
+        Item::new("config").save(deps.storage, &LegacyConfig { admin: info.sender.clone() })?;
+        PLAN_COUNT.save(deps.storage, &1)?;
+        Map::new("plans").save(
+            deps.storage,
+            1u64,
+            &LegacyPlan {
+                id: 1,
+                name: "Old circle".to_string(),
+                description: "Weekly savings circle".to_string(),
+                total_participants: 2,
+                contribution_amount: Uint128::new(100),
+                frequency: Frequency::Weekly,
+                duration_months: 1,
+                trust_score_required: 0,
+                allow_partial: false,
+                participants: vec![alice.to_string(), bob.to_string()],
+                current_cycle: 1,
+                is_active: true,
+                payout_index: 1,
+                balance: Uint128::new(200),
+                created_by: alice.clone(),
+            },
+        )?;
+        // Alice's personal clock is on cycle 1 like the plan's, bob's on cycle 2
+        PARTICIPANT_START.save(deps.storage, (1, alice.clone()), &(now - WEEK - DAY))?;
+        PARTICIPANT_START.save(deps.storage, (1, bob.clone()), &(now - 2 * WEEK - DAY))?;
+        CONTRIBUTIONS.save(deps.storage, (1, alice, 1), &Uint128::new(100))?;
+        CONTRIBUTIONS.save(deps.storage, (1, bob, 2), &Uint128::new(100))?;
+        Ok(Response::new())
+    }
+    fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+#[test]
+fn migrate_moves_legacy_plans_onto_the_shared_clock() {
+    let mut app = App::default();
+    let [alice, bob] = ["alice", "bob"].map(|name| app.api().addr_make(name));
+    app.init_modules(|router, _, storage| router.bank.init_balance(storage, &alice, coins(200, "uxion")).unwrap());
+
+    let legacy_code = app.store_code(legacy_contract());
+    let ajo = app
+        .instantiate_contract(
+            legacy_code,
+            alice.clone(),
+            &[alice.clone(), bob.clone()],
+            &coins(200, "uxion"),
+            "ajo",
+            Some(alice.to_string()),
+        )
+        .unwrap();
+    let code_id = app.store_code(ajo_contract());
+    app.migrate_contract(alice.clone(), ajo.clone(), &MigrateMsg {}, code_id).unwrap();
+    let now = app.block_info().time.seconds();
+
+    let config: Config = app.wrap().query_wasm_smart(&ajo, &QueryMsg::GetConfig {}).unwrap();
+    assert_eq!(config.admin, alice);
+    assert_eq!(config.allowed_denoms, vec![Denom::Native("uxion".to_string())]);
+
+    // Cycle 1 opens now on the shared clock, holding both open buckets, and
+    // bob is still next in line
+    let plan = {
+        let res: PlanResponse = app.wrap().query_wasm_smart(&ajo, &QueryMsg::GetPlan { plan_id: 1 }).unwrap();
+        res.plan.unwrap()
+    };
+    assert_eq!(plan.status, PlanStatus::Active);
+    assert_eq!(plan.start_time, Some(now - WEEK));
+    assert_eq!(plan.total_cycles, 4);
+    assert_eq!(plan.payout_schedule, vec![alice.to_string(), bob.to_string()]);
+    assert!(app.wrap().query_wasm_raw(&ajo, PARTICIPANT_START.key((1, bob.clone())).to_vec()).unwrap().is_none());
+    let pot = app.wrap().query_wasm_raw(&ajo, CYCLE_POT.key((1, 1)).to_vec()).unwrap();
+    assert_eq!(pot.map(|raw| from_json::<Uint128>(raw).unwrap()), Some(Uint128::new(200)));
+
+    // Legacy plans keep pushing payouts when a cycle closes
+    app.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    app.execute_contract(alice.clone(), ajo.clone(), &ExecuteMsg::AdvanceCycle { plan_id: 1 }, &[]).unwrap();
+    assert_eq!(app.wrap().query_balance(&bob, "uxion").unwrap().amount.u128(), 200);
+}
@@ -0,0 +1,69 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, QueryMsg};
+use ajo_contract::state::Config;
+use cosmwasm_std::{coin, coins, Addr, Uint128};
+use cw20::{Denom, UncheckedDenom};
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::{instantiate, NewPlan};
+
+fn usdc_plan() -> ExecuteMsg {
+    NewPlan { denom: Some(UncheckedDenom::Native("uusdc".to_string())), auto_push: Some(true), ..NewPlan::new(2) }.msg()
+}
+
+fn allowed_denoms(app: &App, ajo: &Addr) -> Vec<Denom> {
+    let config: Config = app.wrap().query_wasm_smart(ajo, &QueryMsg::GetConfig {}).unwrap();
+    config.allowed_denoms
+}
+
+#[test]
+fn admin_allowlist_decides_which_denoms_new_plans_use() {
+    let mut app = App::default();
+    let [alice, bob] = ["alice", "bob"].map(|name| app.api().addr_make(name));
+    app.init_modules(|router, _, storage| {
+        for member in [&alice, &bob] {
+            router.bank.init_balance(storage, member, vec![coin(1_000, "uusdc"), coin(1_000, "uxion")]).unwrap();
+        }
+    });
+    let ajo = instantiate(&mut app, &alice);
+    let uxion = Denom::Native("uxion".to_string());
+    let uusdc = Denom::Native("uusdc".to_string());
+    assert_eq!(allowed_denoms(&app, &ajo), vec![uxion.clone()]);
+
+    let err = app.execute_contract(alice.clone(), ajo.clone(), &usdc_plan(), &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::DenomNotAllowed { denom }) if denom == "uusdc"));
+
+    // Only the admin edits the list, and adding twice keeps one entry
+    let add = ExecuteMsg::UpdateAllowedDenoms { add: vec![UncheckedDenom::Native("uusdc".to_string())], remove: vec![] };
+    let err = app.execute_contract(bob.clone(), ajo.clone(), &add, &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::Unauthorized(_))));
+    app.execute_contract(alice.clone(), ajo.clone(), &add, &[]).unwrap();
+    app.execute_contract(alice.clone(), ajo.clone(), &add, &[]).unwrap();
+    assert_eq!(allowed_denoms(&app, &ajo), vec![uxion.clone(), uusdc.clone()]);
+
+    app.execute_contract(alice.clone(), ajo.clone(), &usdc_plan(), &[]).unwrap();
+    app.execute_contract(bob.clone(), ajo.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[]).unwrap();
+
+    // Removing the denom blocks new plans but not the running one
+    let remove = ExecuteMsg::UpdateAllowedDenoms { add: vec![], remove: vec![uusdc] };
+    app.execute_contract(alice.clone(), ajo.clone(), &remove, &[]).unwrap();
+    assert_eq!(allowed_denoms(&app, &ajo), vec![uxion]);
+    let err = app.execute_contract(alice.clone(), ajo.clone(), &usdc_plan(), &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::DenomNotAllowed { .. })));
+
+    let contribute = ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) };
+    let err = app.execute_contract(alice.clone(), ajo.clone(), &contribute, &coins(100, "uxion")).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ContractError>(),
+        Some(ContractError::WrongDenom { expected, received }) if expected == "uusdc" && received == "uxion"
+    ));
+    for member in [&alice, &bob] {
+        app.execute_contract(member.clone(), ajo.clone(), &contribute, &coins(100, "uusdc")).unwrap();
+    }
+
+    // The pot is paid out in the plan's own denom
+    let balance = |who: &Addr, denom: &str| app.wrap().query_balance(who, denom).unwrap().amount.u128();
+    assert_eq!((balance(&alice, "uusdc"), balance(&bob, "uusdc")), (1_100, 900));
+    assert_eq!((balance(&alice, "uxion"), balance(&bob, "uxion")), (1_000, 1_000));
+}
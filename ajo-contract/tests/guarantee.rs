@@ -0,0 +1,156 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, JoinEligibilityResponse, JoinRequestsResponse, QueryMsg};
+use cosmwasm_std::Uint128;
+use cw_multi_test::AppResponse;
+
+mod common;
+use common::{NewPlan, Suite, WEEK};
+
+// Alice creates a three-member weekly plan (100 per share) that requires
+// `trust_score_required`; bob and carol want in, dave and erin vouch
+fn setup(trust_score_required: u64) -> Suite {
+    let plan = NewPlan { trust_score_required, auto_push: Some(true), ..NewPlan::new(3) };
+    Suite::with_plan(&["alice", "bob", "carol", "dave", "erin"], plan)
+}
+
+impl Suite {
+    fn guarantee(
+        &mut self,
+        plan_id: u64,
+        guarantor: usize,
+        member: usize,
+        trust: u64,
+        funds: u128,
+    ) -> Result<AppResponse, ContractError> {
+        let member = self.users[member].to_string();
+        self.execute_with(guarantor, ExecuteMsg::Guarantee { plan_id, member, trust_pledge: Some(trust) }, funds)
+    }
+
+    fn withdraw_guarantee(&mut self, guarantor: usize, member: usize) -> Result<AppResponse, ContractError> {
+        let member = self.users[member].to_string();
+        self.execute(guarantor, ExecuteMsg::WithdrawGuarantee { plan_id: 1, member })
+    }
+
+    fn approve(&mut self, voter: usize, requester: usize) {
+        let requester = self.users[requester].to_string();
+        self.execute(voter, ExecuteMsg::ApproveJoinRequest { plan_id: 1, requester }).unwrap();
+    }
+
+    fn trust_score(&self, who: usize) -> u64 {
+        let user = self.users[who].to_string();
+        self.app.wrap().query_wasm_smart(&self.ajo, &QueryMsg::GetTrustScore { user }).unwrap()
+    }
+}
+
+#[test]
+fn guarantor_pledge_covers_missed_share() {
+    let mut suite = setup(0);
+
+    // Dave vouches for carol's request with funds and ten trust points
+    suite.execute(2, ExecuteMsg::RequestToJoinPlan { plan_id: 1 }).unwrap();
+    let err = suite.guarantee(1, 3, 3, 10, 0).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput(msg) if msg.contains("cannot guarantee themselves")));
+    suite.guarantee(1, 3, 2, 10, 150).unwrap();
+    let res: JoinRequestsResponse =
+        suite.app.wrap().query_wasm_smart(&suite.ajo, &QueryMsg::GetJoinRequests { plan_id: 1 }).unwrap();
+    assert_eq!(res.requests.len(), 1);
+    assert_eq!(res.guarantees.len(), 1);
+    assert_eq!((res.guarantees[0].funds, res.guarantees[0].trust), (Uint128::new(150), 10));
+
+    suite.join(1);
+    suite.approve(0, 2);
+    for who in 0..3 {
+        suite.contribute(who, 100).unwrap();
+    }
+
+    // Cycle 1: carol misses her share and dave's pledge makes bob's pot whole
+    suite.advance(WEEK);
+    suite.contribute(0, 100).unwrap();
+    suite.contribute(1, 100).unwrap();
+    suite.advance(WEEK);
+    suite.execute(0, ExecuteMsg::AdvanceCycle { plan_id: 1 }).unwrap();
+    assert_eq!(suite.balance(1), 1_000 - 200 + 300);
+    assert_eq!(suite.trust_score(3), 40);
+
+    // Locked while carol is still in the running plan
+    let err = suite.withdraw_guarantee(3, 2).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput(msg) if msg.contains("locked")));
+
+    // Cycle 2 completes the plan; dave takes back what was not drawn
+    for who in 0..3 {
+        suite.contribute(who, 100).unwrap();
+    }
+    suite.withdraw_guarantee(3, 2).unwrap();
+    assert_eq!(suite.balance(3), 1_000 - 100);
+}
+
+#[test]
+fn trust_requirement_applies_to_requests_unless_waived() {
+    let mut suite = setup(55);
+
+    for who in [1, 2] {
+        let err = suite.execute(who, ExecuteMsg::RequestToJoinPlan { plan_id: 1 }).unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientTrustScore {}));
+    }
+    let err = suite.execute(1, ExecuteMsg::JoinPlan { plan_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientTrustScore {}));
+
+    // Every participant (so far only alice) votes carol in
+    let carol = suite.users[2].to_string();
+    suite.execute(0, ExecuteMsg::WaiveTrustRequirement { plan_id: 1, member: carol.clone() }).unwrap();
+    let eligibility: JoinEligibilityResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.ajo, &QueryMsg::GetJoinEligibility { plan_id: 1, member: carol })
+        .unwrap();
+    assert_eq!((eligibility.trust_score, eligibility.required), (50, 55));
+    assert!(eligibility.waived && eligibility.eligible);
+
+    suite.execute(2, ExecuteMsg::RequestToJoinPlan { plan_id: 1 }).unwrap();
+    suite.approve(0, 2);
+    assert_eq!(suite.trust_score(2), 52);
+}
+
+#[test]
+fn pledged_trust_is_capped_across_members_and_plans() {
+    let mut suite = setup(0);
+    suite.execute(0, NewPlan::new(3).msg()).unwrap();
+
+    // Dave holds 50 points in all, however they are spread
+    suite.guarantee(1, 3, 2, 30, 0).unwrap();
+    let err = suite.guarantee(1, 3, 1, 30, 0).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput(msg) if msg.contains("more trust")));
+    suite.guarantee(2, 3, 1, 20, 0).unwrap();
+    let err = suite.guarantee(2, 3, 2, 1, 0).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput(msg) if msg.contains("more trust")));
+
+    // Withdrawing a pledge frees its trust again
+    suite.withdraw_guarantee(3, 2).unwrap();
+    suite.guarantee(1, 3, 1, 30, 0).unwrap();
+}
+
+#[test]
+fn guarantors_are_only_drawn_on_until_the_shortfall_is_covered() {
+    let mut suite = setup(0);
+
+    // Dave's and erin's pledges each cover carol's share alone
+    suite.execute(2, ExecuteMsg::RequestToJoinPlan { plan_id: 1 }).unwrap();
+    for guarantor in [3, 4] {
+        suite.guarantee(1, guarantor, 2, 10, 100).unwrap();
+    }
+    suite.join(1);
+    suite.approve(0, 2);
+
+    // Carol misses cycle 0; pledges are drawn in guarantor address order
+    suite.contribute(0, 100).unwrap();
+    suite.contribute(1, 100).unwrap();
+    suite.advance(WEEK);
+    suite.execute(0, ExecuteMsg::AdvanceCycle { plan_id: 1 }).unwrap();
+    let (drawn, spared) = if suite.users[3] < suite.users[4] { (3, 4) } else { (4, 3) };
+    assert_eq!(suite.trust_score(drawn), 40);
+    assert_eq!(suite.trust_score(spared), 50);
+    assert_eq!(suite.balance(0), 1_000 - 100 + 300);
+
+    // The spared guarantor keeps their trust free for other pledges
+    suite.guarantee(1, spared, 1, 40, 0).unwrap();
+}
@@ -0,0 +1,100 @@
+use ajo_contract::msg::{ExecuteMsg, InsurancePoolResponse, ParticipantCycleStatusResponse, QueryMsg, SolvencyResponse};
+use ajo_contract::state::InsuranceClaim;
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::{instantiate, NewPlan, WEEK};
+
+fn contribute(app: &mut App, ajo: &Addr, member: &Addr, sent: u128) {
+    app.execute_contract(
+        member.clone(),
+        ajo.clone(),
+        &ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) },
+        &coins(sent, "uxion"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn pool_covers_missed_share_up_to_its_caps() {
+    let mut app = App::default();
+    let members: Vec<Addr> = ["alice", "bob", "carol"].iter().map(|name| app.api().addr_make(name)).collect();
+    app.init_modules(|router, _, storage| {
+        for member in &members {
+            router.bank.init_balance(storage, member, coins(1_000, "uxion")).unwrap();
+        }
+    });
+    let ajo = instantiate(&mut app, &members[0]);
+
+    app.execute_contract(
+        members[0].clone(),
+        ajo.clone(),
+        &ExecuteMsg::UpdateInsurancePool {
+            premium_bps: Some(5_000),
+            max_cover_per_cycle: Some(Uint128::new(80)),
+            max_cover_per_plan: Some(Uint128::new(150)),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        members[0].clone(),
+        ajo.clone(),
+        &NewPlan { auto_push: Some(true), insured: Some(true), ..NewPlan::new(3) }.msg(),
+        &[],
+    )
+    .unwrap();
+    for member in &members[1..] {
+        app.execute_contract(member.clone(), ajo.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[]).unwrap();
+    }
+
+    // The premium is charged on top of the share
+    let err = app
+        .execute_contract(
+            members[0].clone(),
+            ajo.clone(),
+            &ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) },
+            &coins(100, "uxion"),
+        )
+        .unwrap_err();
+    assert!(err.root_cause().to_string().contains("insurance premium 50"));
+    for member in &members {
+        contribute(&mut app, &ajo, member, 150);
+    }
+
+    // Cycle 1: carol skips her share and the pool covers up to its per-cycle cap
+    app.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    contribute(&mut app, &ajo, &members[0], 150);
+    contribute(&mut app, &ajo, &members[1], 150);
+    app.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    app.execute_contract(members[0].clone(), ajo.clone(), &ExecuteMsg::AdvanceCycle { plan_id: 1 }, &[])
+        .unwrap();
+
+    let bob = app.wrap().query_balance(&members[1], "uxion").unwrap().amount.u128();
+    assert_eq!(bob, 1_000 - 150 - 150 + 280);
+    let status: ParticipantCycleStatusResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &ajo,
+            &QueryMsg::GetParticipantCycleStatus { plan_id: 1, participant: members[2].to_string() },
+        )
+        .unwrap();
+    assert_eq!(status.debt, Uint128::new(20));
+
+    let claims: Vec<InsuranceClaim> =
+        app.wrap().query_wasm_smart(&ajo, &QueryMsg::GetInsuranceClaims { plan_id: 1 }).unwrap();
+    assert_eq!(claims.len(), 1);
+    assert_eq!((claims[0].cycle, claims[0].amount), (1, Uint128::new(80)));
+    assert_eq!(claims[0].members, vec![members[2].clone()]);
+
+    let pool: InsurancePoolResponse = app.wrap().query_wasm_smart(&ajo, &QueryMsg::GetInsurancePool {}).unwrap();
+    assert_eq!(pool.assets[0].reserve, Uint128::new(150 + 100 - 80));
+    assert_eq!(pool.assets[0].claims_paid, Uint128::new(80));
+    assert_eq!(pool.assets[0].exposure, Uint128::new(150 - 80));
+    assert!(pool.solvent);
+
+    let solvency: SolvencyResponse = app.wrap().query_wasm_smart(&ajo, &QueryMsg::GetSolvency {}).unwrap();
+    assert!(solvency.solvent);
+    assert_eq!(solvency.assets[0].tracked, solvency.assets[0].held);
+}
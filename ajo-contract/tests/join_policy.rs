@@ -0,0 +1,149 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, JoinRequestsResponse, QueryMsg};
+use ajo_contract::state::JoinPolicy;
+use cw_multi_test::AppResponse;
+
+mod common;
+use common::{NewPlan, Suite};
+
+// A four-member plan created by alice under `policy`
+fn policy_plan(policy: JoinPolicy) -> Suite {
+    Suite::with_plan(&["alice", "bob", "carol", "dave"], NewPlan { join_policy: Some(policy), ..NewPlan::new(4) })
+}
+
+impl Suite {
+    fn request(&mut self, who: usize) {
+        self.execute(who, ExecuteMsg::RequestToJoinPlan { plan_id: 1 }).unwrap();
+    }
+
+    fn approve(&mut self, voter: usize, requester: usize) -> Result<AppResponse, ContractError> {
+        let requester = self.users[requester].to_string();
+        self.execute(voter, ExecuteMsg::ApproveJoinRequest { plan_id: 1, requester })
+    }
+
+    fn deny(&mut self, voter: usize, requester: usize) -> Result<AppResponse, ContractError> {
+        let requester = self.users[requester].to_string();
+        self.execute(voter, ExecuteMsg::DenyJoinRequest { plan_id: 1, requester })
+    }
+
+    fn participants(&self) -> Vec<String> {
+        self.plan().participants
+    }
+
+    fn pending_requests(&self) -> usize {
+        let res: JoinRequestsResponse =
+            self.app.wrap().query_wasm_smart(&self.ajo, &QueryMsg::GetJoinRequests { plan_id: 1 }).unwrap();
+        res.requests.len()
+    }
+}
+
+#[test]
+fn creator_only_plan_is_decided_by_the_creator() {
+    let mut suite = policy_plan(JoinPolicy::CreatorOnly);
+
+    let err = suite.execute(1, ExecuteMsg::JoinPlan { plan_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized(_)));
+
+    suite.request(1);
+    suite.request(2);
+    // Outsiders cannot vote at all
+    assert!(matches!(suite.approve(3, 1).unwrap_err(), ContractError::NotParticipant {}));
+    suite.approve(0, 1).unwrap();
+    assert_eq!(suite.participants().len(), 2);
+
+    // Bob is in now, but only alice decides
+    assert!(matches!(suite.approve(1, 2).unwrap_err(), ContractError::Unauthorized(_)));
+    suite.deny(0, 2).unwrap();
+    assert_eq!(suite.participants().len(), 2);
+    assert_eq!(suite.pending_requests(), 0);
+}
+
+#[test]
+fn quorum_counts_participants_at_vote_time() {
+    let mut suite = policy_plan(JoinPolicy::Quorum { percent: 60 });
+
+    // 60% of one participant
+    suite.request(1);
+    suite.approve(0, 1).unwrap();
+    assert_eq!(suite.participants().len(), 2);
+
+    // 60% of two participants needs both of them
+    suite.request(2);
+    assert!(matches!(suite.approve(3, 2).unwrap_err(), ContractError::NotParticipant {}));
+    suite.approve(0, 2).unwrap();
+    assert_eq!(suite.participants().len(), 2);
+    suite.approve(1, 2).unwrap();
+    assert_eq!(suite.participants().len(), 3);
+
+    // Two of three refusing leaves too few voters to reach the quorum
+    suite.request(3);
+    suite.deny(1, 3).unwrap();
+    assert_eq!(suite.pending_requests(), 1);
+    suite.deny(2, 3).unwrap();
+    assert_eq!(suite.pending_requests(), 0);
+    assert_eq!(suite.participants().len(), 3);
+}
+
+#[test]
+fn threshold_needs_every_participant_until_it_can_be_reached() {
+    let mut suite = policy_plan(JoinPolicy::Threshold { approvals: 2 });
+    for approvals in [0, 4] {
+        let create = NewPlan { join_policy: Some(JoinPolicy::Threshold { approvals }), ..NewPlan::new(4) }.msg();
+        assert!(matches!(suite.execute(0, create).unwrap_err(), ContractError::InvalidInput(_)));
+    }
+
+    // Nobody joins without a vote, but alone alice's approval decides
+    let err = suite.execute(1, ExecuteMsg::JoinPlan { plan_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized(_)));
+    suite.request(1);
+    suite.approve(0, 1).unwrap();
+    assert_eq!(suite.participants().len(), 2);
+
+    // With two participants it takes both of them
+    suite.request(2);
+    suite.approve(0, 2).unwrap();
+    assert_eq!(suite.pending_requests(), 1);
+    suite.approve(1, 2).unwrap();
+    assert_eq!(suite.participants().len(), 3);
+
+    // From then on two approvals are enough, not every participant
+    suite.request(3);
+    suite.approve(1, 3).unwrap();
+    suite.approve(2, 3).unwrap();
+    assert_eq!(suite.participants().len(), 4);
+}
+
+#[test]
+fn unanimous_plan_is_blocked_by_a_single_refusal() {
+    let mut suite = policy_plan(JoinPolicy::Unanimous);
+
+    let err = suite.execute(1, ExecuteMsg::JoinPlan { plan_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized(_)));
+    suite.request(1);
+    suite.approve(0, 1).unwrap();
+    assert_eq!(suite.participants().len(), 2);
+
+    suite.request(2);
+    suite.approve(0, 2).unwrap();
+    assert_eq!(suite.pending_requests(), 1);
+    suite.deny(1, 2).unwrap();
+    assert_eq!(suite.pending_requests(), 0);
+    assert_eq!(suite.participants().len(), 2);
+}
+
+#[test]
+fn open_plan_admits_directly_and_requests_need_half_of_the_participants() {
+    let mut suite = policy_plan(JoinPolicy::Open);
+
+    suite.join(1);
+    suite.join(2);
+
+    // Two of three participants make up half
+    suite.request(3);
+    assert!(matches!(suite.approve(3, 3).unwrap_err(), ContractError::NotParticipant {}));
+    suite.approve(0, 3).unwrap();
+    assert_eq!(suite.pending_requests(), 1);
+    suite.approve(1, 3).unwrap();
+    assert_eq!(suite.pending_requests(), 0);
+    assert_eq!(suite.participants().len(), 4);
+}
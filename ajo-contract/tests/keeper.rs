@@ -0,0 +1,69 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, PlanResponse, QueryMsg, RefundResponse};
+use ajo_contract::state::{Plan, PlanStatus};
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::{instantiate, NewPlan, WEEK};
+
+fn plan(app: &App, ajo: &Addr) -> Plan {
+    let res: PlanResponse = app.wrap().query_wasm_smart(ajo, &QueryMsg::GetPlan { plan_id: 1 }).unwrap();
+    res.plan.unwrap()
+}
+
+fn balance(app: &App, who: &Addr) -> u128 {
+    app.wrap().query_balance(who, "uxion").unwrap().amount.u128()
+}
+
+#[test]
+fn keepers_are_tipped_from_the_creator_funded_reserve() {
+    let mut app = App::default();
+    let [alice, bob, keeper] = ["alice", "bob", "keeper"].map(|name| app.api().addr_make(name));
+    app.init_modules(|router, _, storage| {
+        for member in [&alice, &bob] {
+            router.bank.init_balance(storage, member, coins(1_000, "uxion")).unwrap();
+        }
+    });
+    let ajo = instantiate(&mut app, &alice);
+    let new_plan = NewPlan { total_cycles: 4, keeper_tip: Some(Uint128::new(10)), auto_push: Some(true), ..NewPlan::new(2) };
+    app.execute_contract(alice.clone(), ajo.clone(), &new_plan.msg(), &[]).unwrap();
+    app.execute_contract(bob.clone(), ajo.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[]).unwrap();
+
+    // Only the creator, who gets the leftover back, funds the reserve
+    let fund = ExecuteMsg::FundKeeperReserve { plan_id: 1 };
+    let err = app.execute_contract(bob.clone(), ajo.clone(), &fund, &coins(25, "uxion")).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::Unauthorized(_))));
+    app.execute_contract(alice.clone(), ajo.clone(), &fund, &coins(25, "uxion")).unwrap();
+    assert_eq!(plan(&app, &ajo).keeper_reserve, Uint128::new(25));
+    let refund: RefundResponse = app
+        .wrap()
+        .query_wasm_smart(&ajo, &QueryMsg::GetRefund { plan_id: 1, participant: alice.to_string() })
+        .unwrap();
+    assert_eq!(refund.claimable, Uint128::new(25));
+
+    let advance = ExecuteMsg::AdvanceCycle { plan_id: 1 };
+    let contribute = ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) };
+
+    // Alice pays every cycle and bob never does, so cycles only close once
+    // their deadline has passed and a keeper advances them
+    let mut tips = Vec::new();
+    for _ in 0..4 {
+        app.execute_contract(alice.clone(), ajo.clone(), &contribute, &coins(100, "uxion")).unwrap();
+        let err = app.execute_contract(keeper.clone(), ajo.clone(), &advance, &[]).unwrap_err();
+        assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::CycleNotDue {})));
+
+        app.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+        let before = balance(&app, &keeper);
+        app.execute_contract(keeper.clone(), ajo.clone(), &advance, &[]).unwrap();
+        tips.push(balance(&app, &keeper) - before);
+    }
+
+    // The last tip takes what is left, after which the reserve is empty
+    assert_eq!(tips, vec![10, 10, 5, 0]);
+    let plan = plan(&app, &ajo);
+    assert_eq!((plan.status, plan.keeper_reserve), (PlanStatus::Completed, Uint128::zero()));
+    assert_eq!((plan.current_cycle, plan.balance), (4, Uint128::zero()));
+    // Each member received one pot of 100 per turn
+    assert_eq!((balance(&app, &alice), balance(&app, &bob)), (1_000 - 25 - 400 + 200, 1_200));
+}
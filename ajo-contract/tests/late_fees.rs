@@ -0,0 +1,122 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, QueryMsg, TrustHistoryResponse};
+use ajo_contract::state::{FeeDestination, LateFee, TrustReason};
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::Uint128;
+use cw_multi_test::AppResponse;
+
+mod common;
+use common::{NewPlan, Suite, DAY};
+
+// A running three-member plan whose shares are due two days into each cycle.
+// Alice, bob and carol are paid out in that order.
+fn setup(late_fee: LateFee, destination: FeeDestination) -> Suite {
+    let plan = NewPlan {
+        auto_push: Some(true),
+        grace_period_seconds: Some(2 * DAY),
+        late_fee: Some(late_fee),
+        late_fee_destination: Some(destination),
+        ..NewPlan::new(3)
+    };
+    let mut suite = Suite::with_plan(&["alice", "bob", "carol"], plan);
+    for who in 1..3 {
+        suite.join(who);
+    }
+    suite
+}
+
+impl Suite {
+    // Pays a 100 share with `sent` attached
+    fn pay_share(&mut self, who: usize, sent: u128) -> Result<AppResponse, ContractError> {
+        self.execute_with(who, ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) }, sent)
+    }
+
+    fn last_trust_reason(&self, who: usize) -> TrustReason {
+        let user = self.users[who].to_string();
+        let res: TrustHistoryResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.ajo, &QueryMsg::GetTrustHistory { user, start_after: None, limit: None })
+            .unwrap();
+        res.events.last().unwrap().event.reason.clone()
+    }
+}
+
+#[test]
+fn shares_inside_the_grace_period_are_on_time_and_later_ones_pay_a_flat_fee_into_the_pot() {
+    let mut suite = setup(LateFee::Flat { amount: Uint128::new(10) }, FeeDestination::Pot);
+
+    // A day into the cycle is still within the grace period
+    suite.advance(DAY);
+    suite.pay_share(0, 100).unwrap();
+    assert_eq!(suite.last_trust_reason(0), TrustReason::OnTimeContribution);
+
+    // Past the deadline the share alone is not enough
+    suite.advance(2 * DAY);
+    assert!(matches!(suite.pay_share(1, 100).unwrap_err(), ContractError::InvalidInput(_)));
+    let res = suite.pay_share(1, 110).unwrap();
+    assert!(res.events.iter().any(|event| event.attributes.iter().any(|attr| attr.key == "late_fee" && attr.value == "10")));
+    assert_eq!(suite.last_trust_reason(1), TrustReason::LateContribution);
+    suite.pay_share(2, 110).unwrap();
+
+    // Both fees ride along with the cycle's payout
+    assert_eq!(suite.balance(0), 900 + 320);
+    assert_eq!((suite.balance(1), suite.balance(2)), (890, 890));
+}
+
+#[test]
+fn bps_fees_go_to_the_next_recipient() {
+    let mut suite = setup(LateFee::Bps { bps: 1_000 }, FeeDestination::Recipient);
+
+    suite.advance(3 * DAY);
+    suite.pay_share(1, 110).unwrap();
+    assert_eq!(suite.balance(0), 1_010);
+    assert_eq!(suite.balance(1), 890);
+
+    // Alice is this cycle's recipient, so her own fee comes straight back
+    suite.pay_share(0, 110).unwrap();
+    suite.pay_share(2, 110).unwrap();
+    assert_eq!(suite.balance(0), 1_010 - 110 + 10 + 10 + 300);
+}
+
+#[test]
+fn fees_can_go_to_a_treasury() {
+    let treasury = MockApi::default().addr_make("treasury");
+    let destination = FeeDestination::Treasury { address: treasury.clone() };
+    let mut suite = setup(LateFee::Flat { amount: Uint128::new(10) }, destination);
+
+    suite.advance(3 * DAY);
+    for who in 0..3 {
+        suite.pay_share(who, 110).unwrap();
+    }
+    assert_eq!(suite.app.wrap().query_balance(&treasury, "uxion").unwrap().amount.u128(), 30);
+    assert_eq!(suite.balance(0), 890 + 300);
+}
+
+#[test]
+fn recipient_fees_wait_for_claim_payout_when_payouts_are_not_pushed() {
+    let plan = NewPlan {
+        grace_period_seconds: Some(2 * DAY),
+        late_fee: Some(LateFee::Flat { amount: Uint128::new(10) }),
+        late_fee_destination: Some(FeeDestination::Recipient),
+        ..NewPlan::new(3)
+    };
+    let mut suite = Suite::with_plan(&["alice", "bob", "carol"], plan);
+    for who in 1..3 {
+        suite.join(who);
+    }
+
+    suite.advance(3 * DAY);
+    suite.pay_share(1, 110).unwrap();
+    assert_eq!(suite.balance(0), 1_000);
+
+    for who in [0, 2] {
+        suite.pay_share(who, 110).unwrap();
+    }
+    suite.execute(0, ExecuteMsg::ClaimPayout { plan_id: 1 }).unwrap();
+    assert_eq!(suite.balance(0), 890 + 300 + 30);
+    assert!(matches!(
+        suite.execute(0, ExecuteMsg::ClaimPayout { plan_id: 1 }).unwrap_err(),
+        ContractError::NothingToClaim {}
+    ));
+}
@@ -0,0 +1,171 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, QueryMsg, RefundResponse};
+use ajo_contract::state::PlanStatus;
+use cosmwasm_std::Uint128;
+
+mod common;
+use common::{NewPlan, Suite, DAY, WEEK};
+
+const NAMES: [&str; 3] = ["alice", "bob", "carol"];
+
+// A two-member plan created by alice; carol stays outside
+fn two_member_plan() -> Suite {
+    Suite::with_plan(&NAMES, NewPlan { auto_push: Some(true), ..NewPlan::new(2) })
+}
+
+impl Suite {
+    fn status(&self) -> PlanStatus {
+        self.plan().status
+    }
+
+    fn refund(&self, who: usize) -> RefundResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.ajo,
+                &QueryMsg::GetRefund { plan_id: 1, participant: self.users[who].to_string() },
+            )
+            .unwrap()
+    }
+}
+
+#[test]
+fn plan_moves_from_recruiting_through_active_to_completed() {
+    let mut suite = two_member_plan();
+    assert_eq!(suite.status(), PlanStatus::Recruiting);
+    assert!(matches!(suite.contribute(0, 100).unwrap_err(), ContractError::PlanNotActive {}));
+
+    // Filling the plan starts it, after which membership is locked
+    suite.join(1);
+    assert_eq!(suite.status(), PlanStatus::Active);
+    assert!(matches!(
+        suite.execute(2, ExecuteMsg::RequestToJoinPlan { plan_id: 1 }).unwrap_err(),
+        ContractError::PlanActive {}
+    ));
+    assert!(matches!(
+        suite.execute(0, ExecuteMsg::StartPlan { plan_id: 1, start_at: None }).unwrap_err(),
+        ContractError::PlanActive {}
+    ));
+
+    for _ in 0..2 {
+        suite.contribute(0, 100).unwrap();
+        suite.contribute(1, 100).unwrap();
+        suite.advance(WEEK);
+    }
+    assert_eq!(suite.status(), PlanStatus::Completed);
+
+    // Nothing moves a finished plan
+    assert!(matches!(suite.contribute(0, 100).unwrap_err(), ContractError::PlanFinished { .. }));
+    assert!(matches!(
+        suite.execute(0, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap_err(),
+        ContractError::PlanFinished { .. }
+    ));
+}
+
+#[test]
+fn plan_can_be_cancelled_while_recruiting_or_active() {
+    // The creator alone cancels before the start
+    let mut suite = two_member_plan();
+    assert!(matches!(
+        suite.execute(1, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap_err(),
+        ContractError::Unauthorized(_)
+    ));
+    suite.execute(0, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap();
+    assert_eq!(suite.status(), PlanStatus::Cancelled);
+    assert!(matches!(
+        suite.execute(1, ExecuteMsg::JoinPlan { plan_id: 1 }).unwrap_err(),
+        ContractError::PlanFinished { .. }
+    ));
+
+    // Once active, it takes a majority of the participants
+    let mut suite = two_member_plan();
+    suite.join(1);
+    suite.contribute(0, 100).unwrap();
+    suite.execute(0, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap();
+    assert_eq!(suite.status(), PlanStatus::Active);
+    suite.execute(1, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap();
+    assert_eq!(suite.status(), PlanStatus::Cancelled);
+    assert!(matches!(suite.contribute(1, 100).unwrap_err(), ContractError::PlanFinished { .. }));
+}
+
+#[test]
+fn creator_can_start_a_partial_plan_up_to_one_cycle_ahead() {
+    let mut suite = Suite::with_plan(&NAMES, NewPlan::new(3));
+    suite.join(1);
+
+    let start = |start_at: u64| ExecuteMsg::StartPlan { plan_id: 1, start_at: Some(start_at) };
+    assert!(matches!(suite.execute(1, start(suite.now())).unwrap_err(), ContractError::Unauthorized(_)));
+    assert!(matches!(suite.execute(0, start(suite.now() - 1)).unwrap_err(), ContractError::InvalidInput(_)));
+    assert!(matches!(suite.execute(0, start(suite.now() + WEEK + 1)).unwrap_err(), ContractError::InvalidInput(_)));
+
+    let start_at = suite.now() + 3 * DAY;
+    suite.execute(0, start(start_at)).unwrap();
+    let plan = suite.plan();
+    assert_eq!((plan.status, plan.start_time), (PlanStatus::Active, Some(start_at)));
+
+    // Membership is locked, and shares are refused until cycle 0 opens
+    assert!(matches!(
+        suite.execute(2, ExecuteMsg::JoinPlan { plan_id: 1 }).unwrap_err(),
+        ContractError::PlanActive {}
+    ));
+    assert!(matches!(
+        suite.contribute(0, 100).unwrap_err(),
+        ContractError::PlanNotStarted { start_time } if start_time == start_at
+    ));
+    suite.advance(3 * DAY);
+    suite.contribute(0, 100).unwrap();
+    assert_eq!(suite.balance(0), 900);
+}
+
+#[test]
+fn member_vote_cancels_a_running_plan_and_refunds_net_contributions() {
+    let mut suite = Suite::with_plan(&NAMES, NewPlan { auto_push: Some(true), ..NewPlan::new(3) });
+    for who in [1, 2] {
+        suite.join(who);
+    }
+
+    // Cycle 0 pays alice 300; in cycle 1 carol does not pay
+    for who in 0..3 {
+        suite.contribute(who, 100).unwrap();
+    }
+    suite.advance(WEEK);
+    suite.contribute(0, 100).unwrap();
+    suite.contribute(1, 100).unwrap();
+    assert_eq!((suite.balance(0), suite.balance(1), suite.balance(2)), (1_100, 800, 900));
+
+    // One vote of three is not a majority, and nobody votes twice
+    suite.execute(0, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap();
+    assert_eq!(suite.status(), PlanStatus::Active);
+    assert!(matches!(
+        suite.execute(0, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap_err(),
+        ContractError::AlreadyVoted {}
+    ));
+    assert!(matches!(
+        suite.execute(1, ExecuteMsg::ClaimRefund { plan_id: 1 }).unwrap_err(),
+        ContractError::InvalidInput(_)
+    ));
+    suite.execute(1, ExecuteMsg::CancelPlan { plan_id: 1 }).unwrap();
+    assert_eq!(suite.status(), PlanStatus::Cancelled);
+
+    // Bob is owed 200 and carol 100, but only cycle 1's 200 is left: shared
+    // pro-rata. Alice already took out more than she put in.
+    let refund = suite.refund(1);
+    assert_eq!(refund.cancel_votes, vec![suite.users[0].clone(), suite.users[1].clone()]);
+    assert_eq!(refund.claimable, Uint128::new(133));
+    assert_eq!(suite.refund(2).claimable, Uint128::new(66));
+    assert_eq!(suite.refund(0).claimable, Uint128::zero());
+
+    suite.execute(1, ExecuteMsg::ClaimRefund { plan_id: 1 }).unwrap();
+    suite.execute(2, ExecuteMsg::ClaimRefund { plan_id: 1 }).unwrap();
+    assert!(matches!(
+        suite.execute(0, ExecuteMsg::ClaimRefund { plan_id: 1 }).unwrap_err(),
+        ContractError::NothingToClaim {}
+    ));
+    assert!(matches!(
+        suite.execute(1, ExecuteMsg::ClaimRefund { plan_id: 1 }).unwrap_err(),
+        ContractError::NothingToClaim {}
+    ));
+    assert_eq!((suite.balance(0), suite.balance(1), suite.balance(2)), (1_100, 933, 966));
+    // Only the rounding dust stays behind
+    assert_eq!(suite.plan().balance, Uint128::one());
+}
@@ -0,0 +1,204 @@
+use ajo_contract::msg::{ExecuteMsg, PayoutScheduleResponse, PlanResponse, QueryMsg};
+use ajo_contract::schedule::{commitment, draw_order, shuffle};
+use ajo_contract::state::{PayoutOrder, PlanStatus};
+use cosmwasm_std::Addr;
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::{instantiate, NewPlan};
+
+fn members(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("member{:02}", i)).collect()
+}
+
+#[test]
+fn shuffle_is_a_deterministic_permutation() {
+    let list = members(12);
+    let seed = [7u8; 32];
+
+    let first = shuffle(&list, &seed);
+    let second = shuffle(&list, &seed);
+    assert_eq!(first, second);
+
+    let mut sorted = first.clone();
+    sorted.sort();
+    assert_eq!(sorted, list);
+    assert_ne!(first, list);
+}
+
+#[test]
+fn shuffle_ignores_stored_member_order() {
+    let list = members(8);
+    let mut reversed = list.clone();
+    reversed.reverse();
+    let mut interleaved: Vec<String> = list.iter().step_by(2).cloned().collect();
+    interleaved.extend(list.iter().skip(1).step_by(2).cloned());
+
+    let seed = [42u8; 32];
+    assert_eq!(shuffle(&list, &seed), shuffle(&reversed, &seed));
+    assert_eq!(shuffle(&list, &seed), shuffle(&interleaved, &seed));
+}
+
+#[test]
+fn shuffle_depends_on_seed() {
+    let list = members(10);
+    assert_ne!(shuffle(&list, &[0u8; 32]), shuffle(&list, &[1u8; 32]));
+}
+
+#[test]
+fn shuffle_handles_tiny_lists() {
+    assert!(shuffle(&[], &[0u8; 32]).is_empty());
+    assert_eq!(shuffle(&members(1), &[0u8; 32]), members(1));
+}
+
+#[test]
+fn draw_order_puts_non_revealers_last() {
+    let list = members(5);
+    let reveals = vec![
+        (list[3].clone(), "delta".to_string()),
+        (list[0].clone(), "alpha".to_string()),
+        (list[4].clone(), "echo".to_string()),
+    ];
+
+    let order = draw_order(1, &list, &reveals);
+    assert_eq!(order.len(), 5);
+    assert_eq!(&order[3..], &[list[1].clone(), list[2].clone()]);
+
+    // Reveal order does not matter, every secret does
+    let mut shuffled_reveals = reveals.clone();
+    shuffled_reveals.reverse();
+    assert_eq!(order, draw_order(1, &list, &shuffled_reveals));
+
+    let mut changed = reveals.clone();
+    changed[0].1 = "other".to_string();
+    let changed_order = draw_order(1, &list, &changed);
+    assert_eq!(&changed_order[3..], &order[3..]);
+}
+
+fn setup_lottery_plan(app: &mut App, names: &[&str]) -> (Addr, Vec<Addr>) {
+    let users: Vec<Addr> = names.iter().map(|name| app.api().addr_make(name)).collect();
+    let contract = instantiate(app, &users[0]);
+
+    app.execute_contract(
+        users[0].clone(),
+        contract.clone(),
+        &NewPlan { payout_order: Some(PayoutOrder::Lottery), ..NewPlan::new(users.len() as u32) }.msg(),
+        &[],
+    )
+    .unwrap();
+    for user in &users[1..] {
+        app.execute_contract(user.clone(), contract.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[])
+            .unwrap();
+    }
+    (contract, users)
+}
+
+fn plan_status(app: &App, contract: &Addr) -> PlanStatus {
+    let res: PlanResponse = app
+        .wrap()
+        .query_wasm_smart(contract, &QueryMsg::GetPlan { plan_id: 1 })
+        .unwrap();
+    res.plan.unwrap().status
+}
+
+fn schedule(app: &App, contract: &Addr) -> Vec<String> {
+    let res: PayoutScheduleResponse = app
+        .wrap()
+        .query_wasm_smart(contract, &QueryMsg::GetPayoutSchedule { plan_id: 1 })
+        .unwrap();
+    res.schedule
+}
+
+#[test]
+fn full_plan_draws_order_by_commit_reveal() {
+    let mut app = App::default();
+    let (contract, users) = setup_lottery_plan(&mut app, &["alice", "bob", "carol"]);
+    assert_eq!(plan_status(&app, &contract), PlanStatus::Drawing);
+
+    let secrets = ["s-alice", "s-bob", "s-carol"];
+    for (user, secret) in users.iter().zip(secrets) {
+        // Nobody can reveal before every member has committed
+        let err = app.execute_contract(
+            user.clone(),
+            contract.clone(),
+            &ExecuteMsg::RevealSecret { plan_id: 1, secret: secret.to_string() },
+            &[],
+        );
+        assert!(err.is_err());
+
+        app.execute_contract(
+            user.clone(),
+            contract.clone(),
+            &ExecuteMsg::CommitSecret { plan_id: 1, commitment: commitment(secret) },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // A secret that does not match the commitment is rejected
+    app.execute_contract(
+        users[0].clone(),
+        contract.clone(),
+        &ExecuteMsg::RevealSecret { plan_id: 1, secret: "wrong".to_string() },
+        &[],
+    )
+    .unwrap_err();
+
+    for (user, secret) in users.iter().zip(secrets) {
+        app.execute_contract(
+            user.clone(),
+            contract.clone(),
+            &ExecuteMsg::RevealSecret { plan_id: 1, secret: secret.to_string() },
+            &[],
+        )
+        .unwrap();
+    }
+    assert_eq!(plan_status(&app, &contract), PlanStatus::Active);
+
+    let members: Vec<String> = users.iter().map(|u| u.to_string()).collect();
+    let reveals: Vec<(String, String)> = members
+        .iter()
+        .cloned()
+        .zip(secrets.iter().map(|s| s.to_string()))
+        .collect();
+    assert_eq!(schedule(&app, &contract), draw_order(1, &members, &reveals));
+}
+
+#[test]
+fn withheld_reveal_is_sent_to_the_back_after_deadline() {
+    let mut app = App::default();
+    let (contract, users) = setup_lottery_plan(&mut app, &["alice", "bob", "carol", "dave"]);
+
+    for user in &users {
+        app.execute_contract(
+            user.clone(),
+            contract.clone(),
+            &ExecuteMsg::CommitSecret { plan_id: 1, commitment: commitment(user.as_str()) },
+            &[],
+        )
+        .unwrap();
+    }
+    // Bob never reveals
+    for user in users.iter().filter(|u| **u != users[1]) {
+        app.execute_contract(
+            user.clone(),
+            contract.clone(),
+            &ExecuteMsg::RevealSecret { plan_id: 1, secret: user.to_string() },
+            &[],
+        )
+        .unwrap();
+    }
+    assert_eq!(plan_status(&app, &contract), PlanStatus::Drawing);
+
+    app.execute_contract(users[2].clone(), contract.clone(), &ExecuteMsg::FinalizeDraw { plan_id: 1 }, &[])
+        .unwrap_err();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(2 * 86_400));
+    app.execute_contract(users[2].clone(), contract.clone(), &ExecuteMsg::FinalizeDraw { plan_id: 1 }, &[])
+        .unwrap();
+
+    assert_eq!(plan_status(&app, &contract), PlanStatus::Active);
+    let order = schedule(&app, &contract);
+    assert_eq!(order.len(), 4);
+    assert_eq!(order[3], users[1].to_string());
+}
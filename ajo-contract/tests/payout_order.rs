@@ -0,0 +1,95 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, PayoutScheduleResponse, QueryMsg};
+use ajo_contract::reputation::ReputationExecuteMsg;
+use ajo_contract::state::PayoutOrder;
+use cw_multi_test::{AppResponse, Executor};
+
+mod common;
+use common::{NewPlan, Suite};
+
+// alice (creator and admin), bob, carol, and an outsider
+const NAMES: [&str; 4] = ["alice", "bob", "carol", "oracle"];
+
+impl Suite {
+    // Three-member plan with the given order; payouts are pushed
+    fn create_plan(&mut self, payout_order: PayoutOrder) {
+        let msg = NewPlan { payout_order: Some(payout_order), auto_push: Some(true), ..NewPlan::new(3) }.msg();
+        self.execute(0, msg).unwrap();
+    }
+
+    fn set_order(&mut self, who: usize, order: &[usize]) -> Result<AppResponse, ContractError> {
+        let order = self.names(order);
+        self.execute(who, ExecuteMsg::SetPayoutOrder { plan_id: 1, order })
+    }
+
+    // Everyone pays cycle 0, which closes it
+    fn pay_cycle(&mut self) {
+        for who in 0..3 {
+            self.contribute(who, 100).unwrap();
+        }
+    }
+
+    fn schedule(&self) -> PayoutScheduleResponse {
+        self.app.wrap().query_wasm_smart(&self.ajo, &QueryMsg::GetPayoutSchedule { plan_id: 1 }).unwrap()
+    }
+
+    fn names(&self, order: &[usize]) -> Vec<String> {
+        order.iter().map(|i| self.users[*i].to_string()).collect()
+    }
+}
+
+#[test]
+fn fixed_order_is_assigned_by_the_creator_and_frozen_at_start() {
+    let mut suite = Suite::new(&NAMES);
+    suite.create_plan(PayoutOrder::Fixed);
+    suite.join(1);
+
+    assert!(matches!(suite.set_order(1, &[1, 0]).unwrap_err(), ContractError::Unauthorized(_)));
+    assert!(matches!(suite.set_order(0, &[1, 3]).unwrap_err(), ContractError::InvalidInput(_)));
+    assert!(matches!(suite.set_order(0, &[1, 1]).unwrap_err(), ContractError::InvalidInput(_)));
+    suite.set_order(0, &[1, 0]).unwrap();
+    let schedule = suite.schedule();
+    assert!(!schedule.frozen);
+    assert_eq!(schedule.schedule, suite.names(&[1, 0]));
+
+    // Carol fills the plan; she was not placed, so she is paid last
+    suite.join(2);
+    let schedule = suite.schedule();
+    assert!(schedule.frozen);
+    assert_eq!(schedule.schedule, suite.names(&[1, 0, 2]));
+    assert_eq!(schedule.next_recipient, Some(suite.users[1].to_string()));
+    assert!(matches!(suite.set_order(0, &[0, 1, 2]).unwrap_err(), ContractError::PlanActive {}));
+
+    suite.pay_cycle();
+    assert_eq!((suite.balance(0), suite.balance(1), suite.balance(2)), (900, 1_200, 900));
+    assert_eq!(suite.schedule().next_recipient, Some(suite.users[0].to_string()));
+}
+
+#[test]
+fn trust_score_order_ranks_members_when_the_plan_starts() {
+    let mut suite = Suite::new(&NAMES);
+    let oracle = suite.users[3].to_string();
+    suite.execute(0, ExecuteMsg::SetAttester { attester: oracle, weight_bps: Some(10_000) }).unwrap();
+    // Carol ends up at 70 and bob at 40; alice stays at the baseline of 50
+    for (who, delta, evidence) in [(2, 20, "a"), (1, -10, "b")] {
+        let attest = ReputationExecuteMsg::SubmitAttestation {
+            subject: suite.users[who].to_string(),
+            delta,
+            evidence_hash: evidence.repeat(64),
+        };
+        suite.app.execute_contract(suite.users[3].clone(), suite.ajo.clone(), &attest, &[]).unwrap();
+    }
+
+    suite.create_plan(PayoutOrder::TrustScore);
+    suite.join(1);
+    assert!(matches!(suite.set_order(0, &[1, 0]).unwrap_err(), ContractError::InvalidInput(_)));
+    // Nothing is ranked until the plan starts
+    assert!(!suite.schedule().frozen);
+    suite.join(2);
+
+    let schedule = suite.schedule();
+    assert_eq!(schedule.order, PayoutOrder::TrustScore);
+    assert_eq!(schedule.schedule, suite.names(&[2, 0, 1]));
+    suite.pay_cycle();
+    assert_eq!((suite.balance(0), suite.balance(1), suite.balance(2)), (900, 900, 1_200));
+}
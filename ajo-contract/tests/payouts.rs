@@ -0,0 +1,76 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, PlanResponse, QueryMsg};
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::{instantiate, NewPlan, WEEK};
+
+fn balance(app: &App, who: &Addr) -> u128 {
+    app.wrap().query_balance(who, "uxion").unwrap().amount.u128()
+}
+
+fn pending(app: &App, ajo: &Addr, recipient: &Addr) -> Uint128 {
+    app.wrap()
+        .query_wasm_smart(ajo, &QueryMsg::GetPendingPayout { plan_id: 1, recipient: recipient.to_string() })
+        .unwrap()
+}
+
+fn plan_balance(app: &App, ajo: &Addr) -> Uint128 {
+    let res: PlanResponse = app.wrap().query_wasm_smart(ajo, &QueryMsg::GetPlan { plan_id: 1 }).unwrap();
+    res.plan.unwrap().balance
+}
+
+#[test]
+fn payouts_wait_for_claims_unless_auto_push_is_on() {
+    let mut app = App::default();
+    let [alice, bob] = ["alice", "bob"].map(|name| app.api().addr_make(name));
+    app.init_modules(|router, _, storage| {
+        for member in [&alice, &bob] {
+            router.bank.init_balance(storage, member, coins(1_000, "uxion")).unwrap();
+        }
+    });
+    let ajo = instantiate(&mut app, &alice);
+    app.execute_contract(alice.clone(), ajo.clone(), &NewPlan::new(2).msg(), &[]).unwrap();
+    app.execute_contract(bob.clone(), ajo.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[]).unwrap();
+    let contribute = |app: &mut App, who: &Addr| {
+        app.execute_contract(
+            who.clone(),
+            ajo.clone(),
+            &ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) },
+            &coins(100, "uxion"),
+        )
+        .unwrap();
+    };
+    let claim = |app: &mut App, who: &Addr| {
+        app.execute_contract(who.clone(), ajo.clone(), &ExecuteMsg::ClaimPayout { plan_id: 1 }, &[])
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+    };
+
+    // Bob's share closes cycle 0, but alice's 200 stays in the contract
+    contribute(&mut app, &alice);
+    contribute(&mut app, &bob);
+    assert_eq!((balance(&app, &alice), balance(&app, &bob)), (900, 900));
+    assert_eq!(pending(&app, &ajo, &alice), Uint128::new(200));
+    assert_eq!(plan_balance(&app, &ajo), Uint128::new(200));
+
+    assert!(matches!(claim(&mut app, &bob).unwrap_err(), ContractError::NothingToClaim {}));
+    claim(&mut app, &alice).unwrap();
+    assert_eq!(balance(&app, &alice), 1_100);
+    assert_eq!(pending(&app, &ajo, &alice), Uint128::zero());
+    assert_eq!(plan_balance(&app, &ajo), Uint128::zero());
+    assert!(matches!(claim(&mut app, &alice).unwrap_err(), ContractError::NothingToClaim {}));
+
+    // Only the creator switches delivery; from then on payouts are sent
+    let auto_push = ExecuteMsg::SetAutoPush { plan_id: 1, enabled: true };
+    let err = app.execute_contract(bob.clone(), ajo.clone(), &auto_push, &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::Unauthorized(_))));
+    app.execute_contract(alice.clone(), ajo.clone(), &auto_push, &[]).unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    contribute(&mut app, &alice);
+    contribute(&mut app, &bob);
+    assert_eq!((balance(&app, &alice), balance(&app, &bob)), (1_000, 1_000));
+    assert_eq!(pending(&app, &ajo, &bob), Uint128::zero());
+    assert_eq!(plan_balance(&app, &ajo), Uint128::zero());
+}
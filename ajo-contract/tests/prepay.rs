@@ -0,0 +1,69 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, ParticipantCycleStatusResponse, PlanResponse, QueryMsg};
+use ajo_contract::state::PlanStatus;
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::{instantiate, NewPlan, WEEK};
+
+fn status(app: &App, ajo: &Addr, who: &Addr) -> ParticipantCycleStatusResponse {
+    app.wrap()
+        .query_wasm_smart(ajo, &QueryMsg::GetParticipantCycleStatus { plan_id: 1, participant: who.to_string() })
+        .unwrap()
+}
+
+fn balance(app: &App, who: &Addr) -> u128 {
+    app.wrap().query_balance(who, "uxion").unwrap().amount.u128()
+}
+
+#[test]
+fn prepaid_shares_fund_later_cycles_as_they_open() {
+    let mut app = App::default();
+    let [alice, bob] = ["alice", "bob"].map(|name| app.api().addr_make(name));
+    app.init_modules(|router, _, storage| {
+        for member in [&alice, &bob] {
+            router.bank.init_balance(storage, member, coins(1_000, "uxion")).unwrap();
+        }
+    });
+    let ajo = instantiate(&mut app, &alice);
+    let plan = NewPlan { total_cycles: 3, auto_push: Some(true), ..NewPlan::new(2) };
+    app.execute_contract(alice.clone(), ajo.clone(), &plan.msg(), &[]).unwrap();
+    app.execute_contract(bob.clone(), ajo.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[]).unwrap();
+
+    let ahead = |cycles: u32| ExecuteMsg::ContributeAhead { plan_id: 1, cycles };
+    let contribute = ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) };
+    let err = app.execute_contract(bob.clone(), ajo.clone(), &ahead(0), &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::InvalidInput(_))));
+    let err = app.execute_contract(bob.clone(), ajo.clone(), &ahead(4), &coins(400, "uxion")).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::InvalidInput(_))));
+
+    // Bob covers the whole plan at once; cycle 0 takes its share right away
+    app.execute_contract(bob.clone(), ajo.clone(), &ahead(3), &coins(300, "uxion")).unwrap();
+    let res = status(&app, &ajo, &bob);
+    assert!(res.fully_contributed);
+    assert_eq!((res.credit, res.prepaid_cycles), (Uint128::new(200), 2));
+    let err = app.execute_contract(bob.clone(), ajo.clone(), &contribute, &coins(100, "uxion")).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::InvalidInput(_))));
+
+    app.execute_contract(alice.clone(), ajo.clone(), &contribute, &coins(100, "uxion")).unwrap();
+    assert_eq!(balance(&app, &alice), 1_100);
+
+    // A week later the credit pays bob's share of cycle 1, so alice's share
+    // completes the pot
+    app.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    app.execute_contract(alice.clone(), ajo.clone(), &contribute, &coins(100, "uxion")).unwrap();
+    let res = status(&app, &ajo, &bob);
+    assert_eq!((res.cycle, res.credit, res.prepaid_cycles), (1, Uint128::new(100), 1));
+    assert_eq!(res.contributed_this_cycle, Uint128::new(100));
+
+    app.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    app.execute_contract(alice.clone(), ajo.clone(), &contribute, &coins(100, "uxion")).unwrap();
+    let res = status(&app, &ajo, &bob);
+    assert_eq!((res.credit, res.missed_cycles), (Uint128::zero(), 0));
+
+    let plan: PlanResponse = app.wrap().query_wasm_smart(&ajo, &QueryMsg::GetPlan { plan_id: 1 }).unwrap();
+    let plan = plan.plan.unwrap();
+    assert_eq!((plan.status, plan.balance), (PlanStatus::Completed, Uint128::zero()));
+    assert_eq!((balance(&app, &alice), balance(&app, &bob)), (1_100, 900));
+}
@@ -0,0 +1,78 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{ExecuteMsg, QueryMsg, TrustBreakdownResponse};
+use ajo_contract::reputation::{ReputationExecuteMsg, ReputationQueryMsg, ReputationResponse};
+use cosmwasm_std::Addr;
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::instantiate;
+
+const EVIDENCE: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+fn reputation(app: &App, ajo: &Addr, subject: &Addr) -> ReputationResponse {
+    app.wrap()
+        .query_wasm_smart(ajo, &ReputationQueryMsg::Reputation { subject: subject.to_string() })
+        .unwrap()
+}
+
+#[test]
+fn registered_attesters_move_scores_once_per_evidence() {
+    let mut app = App::default();
+    let [admin, lender, stranger, alice] = ["admin", "lender", "stranger", "alice"].map(|name| app.api().addr_make(name));
+    let ajo = instantiate(&mut app, &admin);
+
+    let attest = |delta: i64| ReputationExecuteMsg::SubmitAttestation {
+        subject: alice.to_string(),
+        delta,
+        evidence_hash: EVIDENCE.to_string(),
+    };
+
+    // Only the admin registers attesters, and only they can attest
+    let register = ExecuteMsg::SetAttester { attester: lender.to_string(), weight_bps: Some(5_000) };
+    let err = app.execute_contract(stranger.clone(), ajo.clone(), &register, &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::Unauthorized(_))));
+    app.execute_contract(admin.clone(), ajo.clone(), &register, &[]).unwrap();
+    let err = app.execute_contract(stranger.clone(), ajo.clone(), &attest(20), &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::Unauthorized(_))));
+
+    // Deltas must move the score, and by no more than its whole range
+    for delta in [0, 101, -101, i64::MIN] {
+        let err = app.execute_contract(lender.clone(), ajo.clone(), &attest(delta), &[]).unwrap_err();
+        assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::InvalidInput(_))));
+    }
+
+    // A +20 from a half-weight attester is worth 10 points
+    assert_eq!(reputation(&app, &ajo, &alice).updated_at, None);
+    app.execute_contract(lender.clone(), ajo.clone(), &attest(20), &[]).unwrap();
+    let res = reputation(&app, &ajo, &alice);
+    assert_eq!((res.score, res.baseline, res.max_score), (60, 50, 100));
+    assert_eq!(res.updated_at, Some(app.block_info().time.seconds()));
+
+    // The same evidence cannot be replayed, whatever the delta
+    let err = app.execute_contract(lender.clone(), ajo.clone(), &attest(-40), &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::DuplicateAttestation { .. })));
+
+    let breakdown: TrustBreakdownResponse = app
+        .wrap()
+        .query_wasm_smart(&ajo, &QueryMsg::GetTrustBreakdown { user: alice.to_string() })
+        .unwrap();
+    assert_eq!((breakdown.attestation_points, breakdown.decay_points), (10, 0));
+    assert!(breakdown.plans.is_empty());
+
+    // Removed attesters can no longer submit
+    app.execute_contract(
+        admin.clone(),
+        ajo.clone(),
+        &ExecuteMsg::SetAttester { attester: lender.to_string(), weight_bps: None },
+        &[],
+    )
+    .unwrap();
+    let fresh = ReputationExecuteMsg::SubmitAttestation {
+        subject: alice.to_string(),
+        delta: 20,
+        evidence_hash: EVIDENCE.replace('9', "8"),
+    };
+    let err = app.execute_contract(lender.clone(), ajo.clone(), &fresh, &[]).unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::Unauthorized(_))));
+    assert_eq!(reputation(&app, &ajo, &alice).score, 60);
+}
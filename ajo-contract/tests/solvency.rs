@@ -0,0 +1,74 @@
+use ajo_contract::error::ContractError;
+use ajo_contract::msg::{AssetHoldings, ExecuteMsg, QueryMsg, SolvencyResponse};
+use cosmwasm_std::{coin, coins, Addr, BankMsg, Uint128};
+use cw20::Denom;
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::{instantiate, NewPlan};
+
+fn solvency(app: &App, ajo: &Addr) -> SolvencyResponse {
+    app.wrap().query_wasm_smart(ajo, &QueryMsg::GetSolvency {}).unwrap()
+}
+
+fn holdings(tracked: u128, held: u128) -> Vec<AssetHoldings> {
+    vec![AssetHoldings {
+        denom: Denom::Native("uxion".to_string()),
+        tracked: Uint128::new(tracked),
+        held: Uint128::new(held),
+    }]
+}
+
+#[test]
+fn overpayments_are_returned_and_holdings_reconcile() {
+    let mut app = App::default();
+    let [alice, bob, carol] = ["alice", "bob", "carol"].map(|name| app.api().addr_make(name));
+    app.init_modules(|router, _, storage| {
+        for member in [&alice, &bob, &carol] {
+            router.bank.init_balance(storage, member, vec![coin(1_000, "uxion"), coin(1_000, "uatom")]).unwrap();
+        }
+    });
+    let ajo = instantiate(&mut app, &alice);
+    app.execute_contract(alice.clone(), ajo.clone(), &NewPlan::new(3).msg(), &[]).unwrap();
+    for member in [&bob, &carol] {
+        app.execute_contract(member.clone(), ajo.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[]).unwrap();
+    }
+    let balance = |app: &App, who: &Addr| app.wrap().query_balance(who, "uxion").unwrap().amount.u128();
+
+    // Alice attaches 150 for a 100 share and gets 50 back in the same call
+    let contribute = ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) };
+    let res = app.execute_contract(alice.clone(), ajo.clone(), &contribute, &coins(150, "uxion")).unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "refunded" && attr.value == "50")));
+    assert_eq!(balance(&app, &alice), 900);
+
+    // Coins in any other denom are refused outright
+    let err = app
+        .execute_contract(bob.clone(), ajo.clone(), &contribute, &[coin(100, "uxion"), coin(5, "uatom")])
+        .unwrap_err();
+    assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::WrongDenom { .. })));
+    assert_eq!(balance(&app, &bob), 1_000);
+
+    // Prepaying two shares with 250 attached also returns the excess
+    let ahead = ExecuteMsg::ContributeAhead { plan_id: 1, cycles: 2 };
+    app.execute_contract(bob.clone(), ajo.clone(), &ahead, &coins(250, "uxion")).unwrap();
+    assert_eq!(balance(&app, &bob), 800);
+
+    let res = solvency(&app, &ajo);
+    assert!(res.solvent);
+    assert_eq!(res.assets, holdings(300, 300));
+
+    // Stray transfers only make the contract hold more than it tracks
+    app.execute(carol.clone(), BankMsg::Send { to_address: ajo.to_string(), amount: coins(7, "uxion") }.into()).unwrap();
+    let res = solvency(&app, &ajo);
+    assert!(res.solvent);
+    assert_eq!(res.assets, holdings(300, 307));
+
+    // Holdings below the tracked balances are reported
+    app.init_modules(|router, _, storage| router.bank.init_balance(storage, &ajo, coins(250, "uxion")).unwrap());
+    let res = solvency(&app, &ajo);
+    assert!(!res.solvent);
+    assert_eq!(res.assets, holdings(300, 250));
+}
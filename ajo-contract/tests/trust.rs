@@ -0,0 +1,103 @@
+use ajo_contract::msg::{ExecuteMsg, QueryMsg, TrustBreakdownResponse, TrustHistoryResponse};
+use ajo_contract::state::{TrustEvent, TrustReason};
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+mod common;
+use common::{instantiate, NewPlan, DAY};
+
+fn trust_score(app: &App, ajo: &Addr, user: &Addr) -> u64 {
+    app.wrap().query_wasm_smart(ajo, &QueryMsg::GetTrustScore { user: user.to_string() }).unwrap()
+}
+
+fn trust_history(app: &App, ajo: &Addr, user: &Addr, start_after: Option<u64>, limit: Option<u32>) -> TrustHistoryResponse {
+    app.wrap()
+        .query_wasm_smart(ajo, &QueryMsg::GetTrustHistory { user: user.to_string(), start_after, limit })
+        .unwrap()
+}
+
+fn trust_breakdown(app: &App, ajo: &Addr, user: &Addr) -> TrustBreakdownResponse {
+    app.wrap().query_wasm_smart(ajo, &QueryMsg::GetTrustBreakdown { user: user.to_string() }).unwrap()
+}
+
+#[test]
+fn scores_are_clamped_logged_and_decay() {
+    let mut app = App::default();
+    let [alice, bob] = ["alice", "bob"].map(|name| app.api().addr_make(name));
+    app.init_modules(|router, _, storage| {
+        for member in [&alice, &bob] {
+            router.bank.init_balance(storage, member, coins(10_000, "uxion")).unwrap();
+        }
+    });
+    let ajo = instantiate(&mut app, &alice);
+    app.execute_contract(
+        alice.clone(),
+        ajo.clone(),
+        &NewPlan { total_cycles: 8, auto_push: Some(true), ..NewPlan::new(2) }.msg(),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(bob.clone(), ajo.clone(), &ExecuteMsg::JoinPlan { plan_id: 1 }, &[]).unwrap();
+
+    // Alice pays on time six times; bob only pays the first cycle
+    for cycle in 0..6 {
+        app.execute_contract(
+            alice.clone(),
+            ajo.clone(),
+            &ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) },
+            &coins(100, "uxion"),
+        )
+        .unwrap();
+        if cycle == 0 {
+            app.execute_contract(
+                bob.clone(),
+                ajo.clone(),
+                &ExecuteMsg::Contribute { plan_id: 1, amount: Uint128::new(100) },
+                &coins(100, "uxion"),
+            )
+            .unwrap();
+        }
+        app.update_block(|block| block.time = block.time.plus_seconds(7 * DAY));
+    }
+    app.execute_contract(alice.clone(), ajo.clone(), &ExecuteMsg::AdvanceCycle { plan_id: 1 }, &[])
+        .unwrap();
+
+    // Capped at 100; a month in, the last on-time payment only won back the
+    // point that had decayed
+    assert_eq!(trust_score(&app, &ajo, &alice), 100);
+    let first = trust_history(&app, &ajo, &alice, None, Some(4)).events;
+    let rest = trust_history(&app, &ajo, &alice, Some(first[3].id), None).events;
+    let events: Vec<TrustEvent> = first.into_iter().chain(rest).map(|entry| entry.event).collect();
+    assert_eq!(events.len(), 7);
+    assert_eq!(events.iter().filter(|event| event.reason == TrustReason::OnTimeContribution).count(), 6);
+    assert_eq!((events[4].delta, events[4].score), (10, 100));
+    assert_eq!((events[5].reason.clone(), events[5].delta, events[5].score), (TrustReason::Decay, -1, 99));
+    assert_eq!((events[6].delta, events[6].score, events[6].cycle), (1, 100, Some(5)));
+
+    // Five missed cycles floor bob at zero instead of underflowing
+    assert_eq!(trust_score(&app, &ajo, &bob), 0);
+    let events = trust_history(&app, &ajo, &bob, None, None).events;
+    let missed: Vec<i64> = events
+        .iter()
+        .filter(|entry| entry.event.reason == TrustReason::MissedCycle)
+        .map(|entry| entry.event.delta)
+        .collect();
+    assert_eq!(missed, vec![-15, -15, -15, -15, -1]);
+
+    // Both drift back toward the baseline, one point per 30 days
+    app.update_block(|block| block.time = block.time.plus_seconds(90 * DAY));
+    assert_eq!(trust_score(&app, &ajo, &alice), 97);
+    assert_eq!(trust_score(&app, &ajo, &bob), 3);
+
+    // The breakdown accounts for every point between the baseline and the score
+    let breakdown = trust_breakdown(&app, &ajo, &alice);
+    assert_eq!((breakdown.score, breakdown.baseline, breakdown.decay_points), (97, 50, -4));
+    assert_eq!(breakdown.plans.len(), 1);
+    assert_eq!((breakdown.plans[0].on_time, breakdown.plans[0].on_time_points), (6, 51));
+
+    let breakdown = trust_breakdown(&app, &ajo, &bob);
+    let plan = &breakdown.plans[0];
+    assert_eq!((plan.plan_id, plan.on_time, plan.missed, plan.partial, plan.late), (1, 1, 5, 0, 0));
+    assert_eq!((plan.on_time_points, plan.missed_points), (10, -61));
+    assert_eq!(breakdown.decay_points, 4);
+}
@@ -13,7 +13,7 @@ import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { useToast } from "@/hooks/use-toast";
-import { Plan } from "@/types/utils";
+import { Plan, frequencyName } from "@/types/utils";
 import { Coins } from "lucide-react";
 import { useAbstraxionAccount } from "@burnt-labs/abstraxion";
 import { useContract } from "@/context/ContractProvider";
@@ -157,7 +157,7 @@ const ContributeModal = ({ plan, cycleStatus, open, onClose, onSuccess }: Contri
                 </h3>
                 <div className="mt-2 text-sm text-green-700 space-y-1">
                   <p>• Expected: {plan.contribution_amount} uxion</p>
-                  <p>• Frequency: {plan.frequency.toLowerCase()}</p>
+                  <p>• Frequency: {frequencyName(plan.frequency)}</p>
                   {plan.allow_partial && <>
 					<p>Partial payments allowed</p>
 					<p>Contributed: {cycleStatus?.contributed_this_cycle} uxion</p>
@@ -1,4 +1,4 @@
-import { Plan } from '../../types/utils';
+import { Plan, frequencyToText } from '../../types/utils';
 import { Card, CardContent, CardDescription, CardFooter, CardHeader, CardTitle } from '@/components/ui/card';
 import { Progress } from '@/components/ui/progress';
 import { Button } from '@/components/ui/button';
@@ -15,11 +15,6 @@ export const PlanCard = ({ plan }: { plan: Plan }) => {
     participants.map(addr => addr.toLowerCase()).includes(address) ||
     plan.created_by?.toLowerCase() === address;
 
-  const frequencyToText = {
-    Daily: 'day',
-    Weekly: 'week',
-    Monthly: 'month',
-  };
   return (
     <Card className="ajo-card">
       <CardHeader className="pb-2">
@@ -34,7 +29,7 @@ export const PlanCard = ({ plan }: { plan: Plan }) => {
 
           </div>
           <div className="px-3 py-1 rounded-full text-xs font-medium bg-[#5ba88e] text-white">
-            {plan.is_active ? "Active" : "Inactive"}
+            {plan.status}
           </div>
         </div>
       </CardHeader>
@@ -43,13 +38,13 @@ export const PlanCard = ({ plan }: { plan: Plan }) => {
           <div>
             <p className="text-sm text-gray-500">Contribution</p>
             <p className="font-bold text-lg">{plan.contribution_amount} XION</p>
-            <p className="text-xs text-gray-500">per {frequencyToText[plan.frequency]}</p>
+            <p className="text-xs text-gray-500">per {frequencyToText(plan.frequency)}</p>
           </div>
           <div>
             <p className="text-sm text-gray-500">Duration</p>
-            <p className="font-bold text-lg">{plan.duration_months}</p>
+            <p className="font-bold text-lg">{plan.total_cycles}</p>
             <p className="text-xs text-gray-500">
-              {plan.duration_months === 1 ? 'month' : 'months'}
+              {plan.total_cycles === 1 ? 'cycle' : 'cycles'}
             </p>
           </div>
         </div>
@@ -82,12 +77,12 @@ export const PlanCard = ({ plan }: { plan: Plan }) => {
           <Link to={`/groups/${plan.id}`} className="w-full">
             <Button className="w-full" variant="outline">View Details</Button>
           </Link>
-        ) : plan.is_active ? (
+        ) : plan.status === 'Recruiting' ? (
           <Link to={`/groups/${plan.id}`} className="w-full">
             <Button className="w-full bg-[#10B981] hover:bg-[#5ba88e] text-white">Join Plan</Button>
           </Link>
         ) : (
-          <Button disabled className="w-full bg-[#5ba88e]">Group Inactive</Button>
+          <Button disabled className="w-full bg-[#5ba88e]">Not Recruiting</Button>
         )}
       </CardFooter>
     </Card>
@@ -23,6 +23,7 @@ interface ContractContextProps {
   requestJoinPlan: (planId: number) => Promise<ExecuteResult>;
   approveJoinRequest: (planId: number, requester: string) => Promise<ExecuteResult>;
   denyJoinRequest: (planId: number, requester: string) => Promise<ExecuteResult>;
+  cancelPlan: (planId: number) => Promise<ExecuteResult>;
   getJoinRequests: (planId: number) => Promise<{ requests: string[] }>;
   contribute: (planId: number, amountUxion: string) => Promise<ExecuteResult>;
   getParticipantCycleStatus: (planId: number, participant: string) => Promise<ParticipantCycleStatus>;
@@ -167,6 +168,19 @@ export const ContractProvider = ({ children }: { children: ReactNode }) => {
 		);
 	};
 
+	const cancelPlan = async (planId: number): Promise<ExecuteResult> => {
+		if (!signingClient || !sender) throw new Error("You are not signed in");
+
+		return await signingClient.execute(
+			sender,
+			contractAddress,
+			{ CancelPlan: { plan_id: planId } },
+			"auto",
+			"",
+			[]
+		);
+	};
+
 	const contribute = async (planId: number, amountUxion: string) => {
 		if (!signingClient || !sender) throw new Error("You are not signed in");
 		return signingClient.execute(
@@ -206,6 +220,7 @@ export const ContractProvider = ({ children }: { children: ReactNode }) => {
 		getJoinRequests,
 		approveJoinRequest,
 		denyJoinRequest,
+		cancelPlan,
 		contribute,
 		getParticipantCycleStatus,
 		getTrustScore
@@ -41,9 +41,9 @@
       contributions: ['contrib-1', 'contrib-2'],
       max_members: 12,
       frequency: 'Monthly',
-      duration_months: 12,
+      total_cycles: 12,
       totalAmount: 12 * 100 * 12,
-      status: 'Open',
+      status: 'Recruiting',
       trustScoreRequired: 75,
       created_at: new Date('2025-04-01'),
       allowPartial: true,
@@ -60,7 +60,7 @@
       participants: 8,
       contribution_amount: 50,
       frequency: 'Weekly',
-      duration_months: 6,
+      total_cycles: 24,
       totalAmount: 6 * 4 * 50 * 8,
       status: 'Active',
       trustScoreRequired: 85,
@@ -78,10 +78,10 @@
       contribution_amount: 75,
       max_members: 6,
       members: ['user-1', 'user-2'],
-      frequency: 'Biweekly',
-      duration_months: 3,
+      frequency: 'BiWeekly',
+      total_cycles: 6,
       totalAmount: 3 * 2 * 75 * 6,
-      status: 'Open',
+      status: 'Recruiting',
       trustScoreRequired: 70,
       created_at: new Date('2025-04-10'),
       allowPartial: true,
@@ -215,7 +215,7 @@
     totalParticipants: 5,
     contribution_amount: 100,
     frequency: 'Monthly' as const,
-    duration_months: 6,
+    total_cycles: 6,
     trustScoreRequired: 70,
     allowPartial: true,
   };
\ No newline at end of file
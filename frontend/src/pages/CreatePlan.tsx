@@ -46,8 +46,8 @@ const formSchema = z.object({
   description: z.string().min(10).max(500),
   total_participants: z.number().min(2).max(100),
   contribution_amount: z.string().min(1), // string for contract
-  frequency: z.enum(["Daily", "Weekly", "Monthly"]),
-  duration_months: z.number().min(1).max(36),
+  frequency: z.enum(["Daily", "Weekly", "BiWeekly", "Monthly", "Quarterly"]),
+  total_cycles: z.number().min(1).max(1200),
   trust_score_required: z.number().min(0).max(100),
   allow_partial: z.boolean().default(false),
 });
@@ -66,7 +66,7 @@ const CreatePlan = () => {
 		total_participants: 2,
 		contribution_amount: "10",
 		frequency: "Monthly",
-		duration_months: 1,
+		total_cycles: 2,
 		trust_score_required: 0,
 		allow_partial: false,
 	},
@@ -223,7 +223,9 @@ const CreatePlan = () => {
                           <SelectContent>
                             <SelectItem value="Daily">Daily</SelectItem>
                             <SelectItem value="Weekly">Weekly</SelectItem>
+                            <SelectItem value="BiWeekly">Every two weeks</SelectItem>
                             <SelectItem value="Monthly">Monthly</SelectItem>
+                            <SelectItem value="Quarterly">Quarterly</SelectItem>
                           </SelectContent>
                         </Select>
                         <FormMessage />
@@ -233,10 +235,10 @@ const CreatePlan = () => {
 
                   <FormField
                     control={form.control}
-                    name="duration_months"
+                    name="total_cycles"
                     render={({ field }) => (
                       <FormItem>
-                        <FormLabel>Duration (months)</FormLabel>
+                        <FormLabel>Number of cycles</FormLabel>
                         <FormControl>
                           <Input
                             type="number"
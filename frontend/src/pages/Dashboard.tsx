@@ -251,13 +251,13 @@ const Dashboard = () => {
 							animate={{ opacity: 1, y: 0 }}
 							transition={{ duration: 0.4 }}
 						>
-							{userPlans.filter((plan) => plan.is_active).length > 0 ? (
+							{userPlans.filter((plan) => plan.status === 'Active').length > 0 ? (
 							<div className="space-y-6">
 								<h2 className="text-xl font-heading font-semibold text-vox-secondary">
 								Your Active Groups
 								</h2>
 								{userPlans
-								.filter((plan) => plan.is_active)
+								.filter((plan) => plan.status === 'Active')
 								.map((plan) => (
 									<PlanCard key={plan.id} plan={plan} />
 								))}
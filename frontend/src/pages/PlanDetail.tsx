@@ -4,7 +4,7 @@ import TrustScoreBadge from "@/components/shared/TrustScoreBadge";
 import { Button } from "@/components/ui/button";
 import { useToast } from "@/hooks/use-toast";
 import { useContract } from "@/context/ContractProvider";
-import { Plan } from "@/types/utils";
+import { Plan, frequencyName } from "@/types/utils";
 import {
   Card,
   CardContent,
@@ -31,6 +31,7 @@ const PlanDetail = () => {
 	getJoinRequests,
 	approveJoinRequest,
 	denyJoinRequest,
+	cancelPlan,
 	getParticipantCycleStatus,
 	getTrustScore
  } = useContract();
@@ -164,6 +165,24 @@ const PlanDetail = () => {
 			!request.approvals.includes(address) && !request.denials.includes(address)
 	);
 
+	const handleCancelPlan = async () => {
+		try {
+			await cancelPlan(Number(planId));
+			toast({
+				title: "Plan cancelled",
+				description: "The plan is no longer recruiting.",
+			});
+			refetchAll();
+		} catch (err) {
+			console.error(err.message)
+			toast({
+			title: "Cancel failed",
+			description: (err as Error).message,
+			variant: "destructive",
+			});
+		}
+	};
+
   const handleContribute = async () => {
     setContributeModalOpen(true);
   };
@@ -218,13 +237,13 @@ const PlanDetail = () => {
               <div className="flex items-center gap-3">
                 <h1 className="text-3xl font-heading font-bold text-vox-secondary">{plan.name}</h1>
                 <span className="px-3 py-1 rounded-full text-sm font-medium bg-vox-primary/10 text-vox-primary capitalize">
-                  {plan.is_active ? "Active" : "Inactive"}
+                  {plan.status}
                 </span>
               </div>
               <p className="text-vox-secondary/70 mt-2 font-sans">{plan.description}</p>
             </div>
 
-            {!isParticipantOrAdmin && (
+            {!isParticipantOrAdmin && plan.status === 'Recruiting' && (
               <Button
                 className="mt-4 md:mt-0 gradient-bg text-white"
                 onClick={handleJoinPlan}
@@ -233,15 +252,12 @@ const PlanDetail = () => {
               </Button>
             )}
 			
-			{plan?.created_by?.toLowerCase() === address && (
+			{plan?.created_by?.toLowerCase() === address && plan.status === 'Recruiting' && (
 				<Button
 					className="mt-4 md:mt-0 gradient-bg text-white"
-					onClick={() => {
-					// Call your activate/deactivate function here
-					console.log("Toggle group active state");
-					}}
+					onClick={handleCancelPlan}
 				>
-					{plan?.is_active ? "Deactivate" : "Activate"}
+					Cancel Plan
 				</Button>
 			)}
 
@@ -281,13 +297,13 @@ const PlanDetail = () => {
                   <div>
                     <p className="text-sm text-vox-secondary/60">Contribution</p>
                     <p className="font-bold text-lg">{plan.contribution_amount} XION</p>
-                    <p className="text-xs text-vox-secondary/60">{plan.frequency.toLowerCase()}</p>
+                    <p className="text-xs text-vox-secondary/60">{frequencyName(plan.frequency)}</p>
                   </div>
                   <div>
                     <p className="text-sm text-vox-secondary/60">Duration</p>
-                    <p className="font-bold text-lg">{plan.duration_months}</p>
+                    <p className="font-bold text-lg">{plan.total_cycles}</p>
                     <p className="text-xs text-vox-secondary/60">
-                      {plan.duration_months === 1 ? "month" : "months"}
+                      {plan.total_cycles === 1 ? "cycle" : "cycles"}
                     </p>
                   </div>
                 </div>
@@ -354,7 +370,7 @@ const PlanDetail = () => {
                     <CardDescription>Understand the savings rotation process.</CardDescription>
                   </CardHeader>
                   <CardContent className="space-y-4">
-                    <p>This is a {plan.frequency.toLowerCase()} group plan where each member contributes {plan.contribution_amount} XION for {plan.duration_months} {plan.duration_months === 1 ? "month" : "months"}.</p>
+                    <p>This is a {frequencyName(plan.frequency)} group plan where each member contributes {plan.contribution_amount} XION for {plan.total_cycles} {plan.total_cycles === 1 ? "cycle" : "cycles"}.</p>
                     <p>One member gets the pooled amount each cycle. The order is based on trust scores and join order.</p>
                   </CardContent>
                 </Card>
@@ -14,7 +14,7 @@ import { Search, Plus } from 'lucide-react';
 import { Link } from 'react-router-dom';
 import { motion, AnimatePresence } from 'framer-motion';
 import { useContract } from '@/context/ContractProvider';
-import { Plan } from '@/types/utils';
+import { Plan, isPlanFinished } from '@/types/utils';
 
 const Plans = () => {
   const [searchTerm, setSearchTerm] = useState('');
@@ -52,8 +52,9 @@ const Plans = () => {
 
     const matchesStatus =
       statusFilter === 'all' ||
-      (statusFilter === 'active' && plan.is_active) ||
-      (statusFilter === 'inactive' && !plan.is_active);
+      (statusFilter === 'recruiting' && plan.status === 'Recruiting') ||
+      (statusFilter === 'active' && (plan.status === 'Active' || plan.status === 'Drawing')) ||
+      (statusFilter === 'finished' && isPlanFinished(plan.status));
 
     return matchesSearch && matchesStatus;
   });
@@ -87,8 +88,9 @@ const Plans = () => {
             </SelectTrigger>
             <SelectContent>
               <SelectItem value="all">All Status</SelectItem>
+              <SelectItem value="recruiting">Recruiting</SelectItem>
               <SelectItem value="active">Active</SelectItem>
-              <SelectItem value="inactive">Inactive</SelectItem>
+              <SelectItem value="finished">Finished</SelectItem>
             </SelectContent>
           </Select>
         </motion.div>
@@ -1,6 +1,7 @@
 import XionWalletService from './blockchain';
 import { Plan } from '../lib/mock-data';
 import { TreasuryService } from './treasuryService';
+import { Frequency } from '../types/utils';
 
 // Smart contract parameters
 interface ContractParams {
@@ -8,8 +9,8 @@ interface ContractParams {
   description: string;
   totalParticipants: number;
   contributionAmount: number;
-  frequency: 'Daily' | 'Weekly' | 'Biweekly' | 'Monthly';
-  duration: number;
+  frequency: Frequency;
+  totalCycles: number;
   trustScoreRequired: number;
   allowPartial: boolean;
   initiatorAddress: string;
@@ -51,7 +52,7 @@ export class ContractService {
         total_participants: plan.totalParticipants,
         contribution_amount: plan.contributionAmount,
         frequency: plan.frequency,
-        duration_months: plan.duration,
+        total_cycles: plan.totalCycles,
         trust_score_required: plan.trustScoreRequired,
         allow_partial: plan.allowPartial,
       };
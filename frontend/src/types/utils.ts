@@ -1,14 +1,61 @@
+// Mirrors the contract's `Frequency`: unit variants serialize as strings,
+// `Custom` as `{ Custom: { seconds } }`. Monthly and Quarterly cycles follow
+// calendar months.
+export type Frequency =
+  | 'Daily'
+  | 'Weekly'
+  | 'BiWeekly'
+  | 'Monthly'
+  | 'Quarterly'
+  | { Custom: { seconds: number } };
+
+// Length of one cycle, e.g. "week" or "3 days"
+export const frequencyToText = (frequency: Frequency): string => {
+  if (typeof frequency !== 'string') {
+    const { seconds } = frequency.Custom;
+    if (seconds % 86400 === 0) return `${seconds / 86400} days`;
+    if (seconds % 3600 === 0) return `${seconds / 3600} hours`;
+    return `${seconds} seconds`;
+  }
+  return {
+    Daily: 'day',
+    Weekly: 'week',
+    BiWeekly: '2 weeks',
+    Monthly: 'month',
+    Quarterly: 'quarter',
+  }[frequency];
+};
+
+// Adjective for the plan, e.g. "weekly"
+export const frequencyName = (frequency: Frequency): string =>
+  typeof frequency === 'string'
+    ? frequency === 'BiWeekly' ? 'bi-weekly' : frequency.toLowerCase()
+    : `every ${frequencyToText(frequency)}`;
+
 export interface CreatePlanInput {
   name: string;
   description: string;
   total_participants: number;
   contribution_amount: string;
-  frequency: string;
-  duration_months: number;
+  frequency: Frequency;
+  total_cycles: number;
   trust_score_required: number;
   allow_partial: boolean;
 }
 
+// Mirrors the contract's `PlanStatus`: plans take members while Recruiting,
+// lottery plans pass through Drawing, and the last three are final
+export type PlanStatus =
+  | 'Recruiting'
+  | 'Drawing'
+  | 'Active'
+  | 'Completed'
+  | 'Cancelled'
+  | 'Dissolved';
+
+export const isPlanFinished = (status: PlanStatus) =>
+  status === 'Completed' || status === 'Cancelled' || status === 'Dissolved';
+
 export interface Plan {
     id: string;
     name: string;
@@ -17,12 +64,13 @@ export interface Plan {
     total_participants: number;
     participants: string[];
     contribution_amount: string;
-    frequency: 'Daily' | 'Weekly' | 'Monthly';
-    duration_months: number;
+    frequency: Frequency;
+    total_cycles: number;
     trust_score_required: number;
     allow_partial: boolean;
 	current_cycle: number;
-	is_active: boolean;
+	status: PlanStatus;
+	start_time: number | null; // shared cycle-0 start, in seconds
 	payout_index: number;
 }
 